@@ -0,0 +1,60 @@
+use persisten_tui::app::{self, App};
+use persisten_tui::componets::component::Component;
+use persisten_tui::events::{AppEventSender, Event};
+use persisten_tui::widgets::{Button, InputWidget, Widget};
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+enum Msg {
+    Greet(String),
+    Clear,
+}
+
+struct Greeter {
+    root: Component,
+    sender: Option<AppEventSender<Msg>>,
+}
+
+impl App for Greeter {
+    type Message = Msg;
+
+    fn init(&mut self, sender: AppEventSender<Msg>) -> color_eyre::Result<()> {
+        let name = InputWidget::new("name").on_enter(&sender, Msg::Greet);
+        let clear = Button::new("clear", 'c').on_press(&sender, Msg::Clear);
+        self.root = Component::ListView(Rc::from([
+            Component::from(name),
+            Component::Widget(clear.boxed()),
+        ]));
+        self.sender = Some(sender);
+        Ok(())
+    }
+
+    fn root(&self) -> Component {
+        self.root.clone()
+    }
+
+    fn handle_event(&mut self, event: Event<Msg>) -> color_eyre::Result<()> {
+        let Some(sender) = self.sender.as_ref() else {
+            return Ok(());
+        };
+        match event {
+            Event::Message(Msg::Greet(name)) => sender.info(&format!("Hello {name}!")),
+            Event::Message(Msg::Clear) => {
+                for widget in self.root.iter() {
+                    widget.borrow_mut().clear(true);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> color_eyre::Result<()> {
+    let mut app = Greeter {
+        root: Component::ListView(Rc::from([])),
+        sender: None,
+    };
+    app::run(&mut app).await
+}
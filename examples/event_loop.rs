@@ -6,7 +6,7 @@ use persisten_tui::events::Event;
 async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
 
-    let mut event_handler = events::EventHandler::new();
+    let mut event_handler: events::EventHandler = events::EventHandler::new();
 
     loop {
         let event = event_handler.next().await?;
@@ -0,0 +1,48 @@
+use persisten_tui::app::{App, Runner};
+use persisten_tui::componets::component::{Component, WidgetType};
+use persisten_tui::events::{AppEvent, AppEventSender, Event};
+use persisten_tui::widgets::{InputWidget, Widget, WidgetEvent};
+
+/// Asks for a name below the shell prompt, the answer stays in the scrollback.
+struct Prompt {
+    input: WidgetType,
+    sender: Option<AppEventSender>,
+}
+
+impl App for Prompt {
+    type Message = ();
+
+    fn init(&mut self, sender: AppEventSender) -> color_eyre::Result<()> {
+        self.input.borrow_mut().focus();
+        self.sender = Some(sender);
+        Ok(())
+    }
+
+    fn root(&self) -> Component {
+        Component::Widget(self.input.clone())
+    }
+
+    fn handle_event(&mut self, _: Event) -> color_eyre::Result<()> {
+        Ok(())
+    }
+
+    fn handle_widget_event(&mut self, event: WidgetEvent) -> color_eyre::Result<()> {
+        if let (WidgetEvent::Input(_), Some(sender)) = (event, self.sender.as_ref()) {
+            sender.send(AppEvent::Quit);
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> color_eyre::Result<()> {
+    let mut prompt = Prompt {
+        input: InputWidget::new("Your name").boxed(),
+        sender: None,
+    };
+    Runner::new()
+        .inline(3)
+        .mouse_capture(false)
+        .run(&mut prompt)
+        .await
+}
@@ -0,0 +1,493 @@
+use crate::componets::component::Component;
+use crate::componets::focus::FocusManager;
+use crate::componets::mouse::{HitMap, MouseRouter};
+use crate::componets::toast::Toasts;
+use crate::events::{AppEvent, AppEventSender, Event, EventHandler, EventHandlerBuilder};
+use crate::widgets::WidgetEvent;
+use crossterm::event::{
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers,
+};
+use crossterm::style::Print;
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute};
+use ratatui::backend::CrosstermBackend;
+use ratatui::{TerminalOptions, Viewport};
+use std::io::{self, Stdout, stdout};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// Action that suspends the application like Ctrl+Z, which is handled even without a binding.
+pub const SUSPEND_ACTION: &str = "suspend";
+
+/// What [`restore`] has to undo, so a panic restores the terminal the way it was set up.
+///
+/// Values from [`INLINE`] on hold the row below the inline viewport.
+static SCREEN: AtomicU32 = AtomicU32::new(UNKNOWN);
+/// Not set up by a [`Runner`], [`restore`] leaves the alternate screen to be safe.
+const UNKNOWN: u32 = 0;
+const ALTERNATE: u32 = 1;
+/// Already restored, the screen is left alone.
+const RESTORED: u32 = 2;
+const INLINE: u32 = 3;
+
+/// An application driven by [`run`].
+pub trait App {
+    type Message: Send + 'static;
+
+    /// Called once before the first frame, `sender` reaches the application from widgets and
+    /// background tasks.
+    fn init(&mut self, _sender: AppEventSender<Self::Message>) -> color_eyre::Result<()> {
+        Ok(())
+    }
+
+    /// The tree that is drawn and receives the input, asked for again for every event.
+    fn root(&self) -> Component;
+
+    /// Handles an event the widgets did not use.
+    ///
+    /// Keys go to the focused widget if there is one, so the application only sees them when
+    /// nothing has focus. Use [`Keymap`](crate::events::keymap::Keymap) actions for shortcuts
+    /// that work everywhere.
+    fn handle_event(&mut self, event: Event<Self::Message>) -> color_eyre::Result<()>;
+
+    /// Called when a widget reports what happened, e.g. that an input was submitted.
+    fn handle_widget_event(&mut self, _event: WidgetEvent) -> color_eyre::Result<()> {
+        Ok(())
+    }
+
+    /// Called for [`AppEvent::Quit`], return `false` to keep running, e.g. to ask first.
+    fn quit(&mut self) -> bool {
+        true
+    }
+}
+
+/// Runs `app` in the alternate screen with the default [`Runner`].
+pub async fn run<A: App>(app: &mut A) -> color_eyre::Result<()> {
+    Runner::new().run(app).await
+}
+
+/// Owns the terminal and the event loop of an [`App`].
+///
+/// The terminal is put into raw mode and the alternate screen with mouse capture and restored
+/// when the app quits, fails or panics, see [`install_hooks`]. Keys, clicks and pastes are
+/// delivered to the widgets of [`App::root`], the [`FocusManager`] moves the focus and
+/// notifications are shown with [`Toasts`]. The tree is drawn after every event, or only on
+/// [`Event::Render`] if a render rate is set.
+///
+/// With [`Runner::inline`] the tree is drawn below the shell prompt instead of the alternate
+/// screen and stays in the scrollback when the app quits.
+pub struct Runner<M = ()> {
+    events: EventHandlerBuilder<M>,
+    focus: FocusManager,
+    toasts: Toasts,
+    mouse_capture: bool,
+    /// Height of the inline viewport, the alternate screen is used if not set.
+    inline: Option<u16>,
+}
+
+impl<M: Send + 'static> Default for Runner<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: Send + 'static> Runner<M> {
+    pub fn new() -> Self {
+        Self {
+            events: EventHandler::builder(),
+            focus: FocusManager::new(),
+            toasts: Toasts::new(),
+            mouse_capture: true,
+            inline: None,
+        }
+    }
+
+    /// Builds the [`EventHandler`] from `events`, e.g. to change the keymap or the rates.
+    pub fn events(mut self, events: EventHandlerBuilder<M>) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub fn focus(mut self, focus: FocusManager) -> Self {
+        self.focus = focus;
+        self
+    }
+
+    pub fn toasts(mut self, toasts: Toasts) -> Self {
+        self.toasts = toasts;
+        self
+    }
+
+    /// Whether mouse events are reported, enabled by default. Capturing the mouse disables the
+    /// text selection of the terminal.
+    pub fn mouse_capture(mut self, enabled: bool) -> Self {
+        self.mouse_capture = enabled;
+        self
+    }
+
+    /// Draws into `height` lines below the cursor instead of the alternate screen, e.g. for a
+    /// quick prompt in a script. The last frame is left in the scrollback.
+    pub fn inline(mut self, height: u16) -> Self {
+        self.inline = Some(height);
+        self
+    }
+
+    /// Runs `app` until it quits.
+    pub async fn run<A: App<Message = M>>(self, app: &mut A) -> color_eyre::Result<()> {
+        install_hooks();
+        // before the event task starts reading, the inline viewport asks for the cursor position
+        let mut terminal = Terminal::enter(self.mouse_capture, self.inline)?;
+        let mut events = self.events.build();
+        let mut session = Session::new(self.focus, self.toasts);
+        let result = async {
+            app.init(events.get_event_sender().into())?;
+            let draw_on_render = events.rates().render_fps.is_some();
+            terminal.draw(|frame| session.draw(app, frame))?;
+            loop {
+                let event = events.next().await?;
+                let redraw = !draw_on_render
+                    || matches!(
+                        event,
+                        Event::Render | Event::Crossterm(CrosstermEvent::Resize(..))
+                    );
+                match session.handle(app, event)? {
+                    Flow::Continue => {}
+                    Flow::Suspend => terminal.suspend()?,
+                    Flow::Quit => break,
+                }
+                if redraw {
+                    terminal.draw(|frame| session.draw(app, frame))?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+        events.stop();
+        // restores the terminal, also on errors
+        drop(terminal);
+        result
+    }
+}
+
+/// What the event loop does after an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Suspend,
+    Quit,
+}
+
+/// Input routing state of a running app.
+struct Session {
+    hits: HitMap,
+    mouse: MouseRouter,
+    focus: FocusManager,
+    toasts: Toasts,
+}
+
+impl Session {
+    fn new(focus: FocusManager, toasts: Toasts) -> Self {
+        Self {
+            hits: HitMap::new(),
+            mouse: MouseRouter::new(),
+            focus,
+            toasts,
+        }
+    }
+
+    fn handle<A: App>(
+        &mut self,
+        app: &mut A,
+        event: Event<A::Message>,
+    ) -> color_eyre::Result<Flow> {
+        if self.toasts.handle_event(&event) {
+            return Ok(Flow::Continue);
+        }
+        let root = app.root();
+        let widget_event = match &event {
+            Event::App(AppEvent::Quit) if app.quit() => return Ok(Flow::Quit),
+            Event::App(AppEvent::Quit) => return Ok(Flow::Continue),
+            Event::App(AppEvent::Action(action)) if action == SUSPEND_ACTION => {
+                return Ok(Flow::Suspend);
+            }
+            Event::App(AppEvent::KeyEvent(key)) if is_suspend(key) => return Ok(Flow::Suspend),
+            Event::App(AppEvent::FocusItem(target)) => {
+                self.focus.focus_ref(&root, target);
+                return Ok(Flow::Continue);
+            }
+            Event::App(AppEvent::KeyEvent(key)) => {
+                if self.focus.handle_key_event(&root, *key).is_some() {
+                    return Ok(Flow::Continue);
+                }
+                match root.focused() {
+                    Some(widget) => widget.borrow_mut().handle_key_event(*key),
+                    None => return Self::forward(app, event),
+                }
+            }
+            Event::Crossterm(CrosstermEvent::Mouse(mouse)) => {
+                self.mouse.handle_mouse_event(&root, &self.hits, *mouse)
+            }
+            Event::Tick => {
+                for widget in root.iter() {
+                    widget.borrow_mut().tick();
+                }
+                return Self::forward(app, event);
+            }
+            Event::Crossterm(CrosstermEvent::Paste(text)) if root.focused().is_some() => {
+                root.handle_paste(text)
+            }
+            _ => return Self::forward(app, event),
+        };
+        if let Some(widget_event) = widget_event {
+            app.handle_widget_event(widget_event)?;
+        }
+        Ok(Flow::Continue)
+    }
+
+    fn forward<A: App>(app: &mut A, event: Event<A::Message>) -> color_eyre::Result<Flow> {
+        app.handle_event(event)?;
+        Ok(Flow::Continue)
+    }
+
+    fn draw<A: App>(&mut self, app: &A, frame: &mut ratatui::Frame) {
+        app.root().render_frame(frame, &mut self.hits);
+        self.toasts.render(frame.area(), frame.buffer_mut());
+    }
+}
+
+fn is_suspend(key: &KeyEvent) -> bool {
+    key.is_press() && key.code == KeyCode::Char('z') && key.modifiers == KeyModifiers::CONTROL
+}
+
+/// The terminal in raw mode and the alternate screen or an inline viewport, restored when
+/// dropped.
+struct Terminal {
+    inner: ratatui::Terminal<CrosstermBackend<Stdout>>,
+    mouse_capture: bool,
+    inline: Option<u16>,
+}
+
+impl Terminal {
+    fn enter(mouse_capture: bool, inline: Option<u16>) -> color_eyre::Result<Self> {
+        let mut terminal = Self {
+            inner: Self::backend(inline)?,
+            mouse_capture,
+            inline,
+        };
+        terminal.setup()?;
+        terminal.inner.clear()?;
+        terminal.publish();
+        Ok(terminal)
+    }
+
+    /// Tells [`restore`] how to leave this terminal.
+    fn publish(&mut self) {
+        let screen = match self.inline {
+            Some(_) => INLINE + u32::from(self.inner.get_frame().area().bottom()),
+            None => ALTERNATE,
+        };
+        SCREEN.store(screen, Ordering::SeqCst);
+    }
+
+    /// An inline viewport starts at the line of the cursor, so it has to be created again
+    /// whenever the shell moved the cursor.
+    fn backend(inline: Option<u16>) -> io::Result<ratatui::Terminal<CrosstermBackend<Stdout>>> {
+        let viewport = match inline {
+            Some(height) => Viewport::Inline(height),
+            None => Viewport::Fullscreen,
+        };
+        ratatui::Terminal::with_options(
+            CrosstermBackend::new(stdout()),
+            TerminalOptions { viewport },
+        )
+    }
+
+    fn setup(&mut self) -> io::Result<()> {
+        enable_modes(self.inline.is_none(), self.mouse_capture)
+    }
+
+    fn leave(&mut self) -> io::Result<()> {
+        // the viewport may have moved since it was published, e.g. after a resize
+        self.publish();
+        restore()
+    }
+
+    fn draw(&mut self, render: impl FnOnce(&mut ratatui::Frame)) -> io::Result<()> {
+        self.inner.draw(render)?;
+        Ok(())
+    }
+
+    /// Gives the terminal back to the shell until the process is continued, like Ctrl+Z in a
+    /// cooked terminal.
+    fn suspend(&mut self) -> color_eyre::Result<()> {
+        self.leave()?;
+        #[cfg(unix)]
+        // SAFETY: raising a signal has no memory safety requirements, the call returns once the
+        // shell continues the process
+        unsafe {
+            libc::raise(libc::SIGTSTP);
+        }
+        if self.inline.is_some() {
+            self.inner = Self::backend(self.inline)?;
+        }
+        self.setup()?;
+        // the screen was used by the shell in between
+        self.inner.clear()?;
+        self.publish();
+        Ok(())
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        // there is nobody left to report a failure to
+        let _ = self.leave();
+    }
+}
+
+/// Puts the terminal into raw mode and the alternate screen with bracketed paste, and mouse
+/// capture if `mouse_capture` is set.
+///
+/// For event loops that read an [`EventHandler`] without a [`Runner`], which does this
+/// itself. Undo it with [`restore`].
+pub fn setup(mouse_capture: bool) -> io::Result<()> {
+    SCREEN.store(ALTERNATE, Ordering::SeqCst);
+    enable_modes(true, mouse_capture)
+}
+
+fn enable_modes(alternate_screen: bool, mouse_capture: bool) -> io::Result<()> {
+    terminal::enable_raw_mode()?;
+    if alternate_screen {
+        execute!(stdout(), EnterAlternateScreen)?;
+    }
+    execute!(stdout(), EnableBracketedPaste)?;
+    if mouse_capture {
+        execute!(stdout(), EnableMouseCapture)?;
+    }
+    Ok(())
+}
+
+/// Leaves raw mode and shows the cursor again.
+///
+/// While a [`Runner`] uses an inline viewport the cursor moves to the line below it, so the last
+/// frame stays in the scrollback, otherwise the alternate screen is left.
+pub fn restore() -> io::Result<()> {
+    let screen = SCREEN.swap(RESTORED, Ordering::SeqCst);
+    terminal::disable_raw_mode()?;
+    execute!(
+        stdout(),
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        cursor::Show
+    )?;
+    match screen {
+        RESTORED => Ok(()),
+        UNKNOWN | ALTERNATE => execute!(stdout(), LeaveAlternateScreen),
+        // continue on the line below the viewport, the last frame stays above it
+        bottom => execute!(
+            stdout(),
+            cursor::MoveTo(0, (bottom - INLINE).saturating_sub(1) as u16),
+            Print("\r\n")
+        ),
+    }
+}
+
+/// Installs the color-eyre hooks, the panic hook restores the terminal before the panic is
+/// printed.
+///
+/// Errors returned by [`Runner::run`] are printed by the caller, after the terminal was
+/// restored, so the error hook does not need to restore it. If the application installed its
+/// own error hook already, it is kept. Called by [`run`], only the first call has an effect.
+pub fn install_hooks() {
+    static INSTALLED: AtomicBool = AtomicBool::new(false);
+    if INSTALLED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let (panic_hook, eyre_hook) = color_eyre::config::HookBuilder::default().into_hooks();
+    let panic_hook = panic_hook.into_panic_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore();
+        panic_hook(info);
+    }));
+    let _ = eyre_hook.install();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::componets::toast::Toast;
+    use crate::widgets::{InputWidget, Widget};
+
+    #[derive(Debug, Default)]
+    struct Counter {
+        keys: usize,
+        input: Option<crate::componets::component::WidgetType>,
+    }
+
+    impl App for Counter {
+        type Message = ();
+
+        fn root(&self) -> Component {
+            Component::Widget(self.input.clone().unwrap())
+        }
+
+        fn handle_event(&mut self, event: Event) -> color_eyre::Result<()> {
+            if let Event::App(AppEvent::KeyEvent(_)) = event {
+                self.keys += 1;
+            }
+            Ok(())
+        }
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::App(AppEvent::KeyEvent(KeyEvent::new(code, modifiers)))
+    }
+
+    #[test]
+    fn routes_events() {
+        let input = InputWidget::new("name").boxed();
+        let mut app = Counter {
+            keys: 0,
+            input: Some(input.clone()),
+        };
+        let mut session = Session::new(FocusManager::new(), Toasts::new());
+        let flow =
+            |session: &mut Session, app: &mut Counter, event| session.handle(app, event).unwrap();
+
+        // nothing has focus, the key goes to the app
+        flow(
+            &mut session,
+            &mut app,
+            key(KeyCode::Char('a'), KeyModifiers::NONE),
+        );
+        assert_eq!(app.keys, 1);
+        flow(
+            &mut session,
+            &mut app,
+            key(KeyCode::Tab, KeyModifiers::NONE),
+        );
+        flow(
+            &mut session,
+            &mut app,
+            key(KeyCode::Char('a'), KeyModifiers::NONE),
+        );
+        assert_eq!(app.keys, 1);
+        assert_eq!(input.borrow().save_state().unwrap()["value"], "a");
+
+        let toast = Event::App(AppEvent::Toast(Toast::info("saved")));
+        assert_eq!(flow(&mut session, &mut app, toast), Flow::Continue);
+        assert_eq!(session.toasts.active().count(), 1);
+        assert_eq!(
+            flow(
+                &mut session,
+                &mut app,
+                key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+            ),
+            Flow::Suspend
+        );
+        assert_eq!(
+            flow(&mut session, &mut app, Event::App(AppEvent::Quit)),
+            Flow::Quit
+        );
+    }
+}
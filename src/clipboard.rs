@@ -0,0 +1,170 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+/// Text clipboard used by the widgets for copy, cut and paste.
+pub trait Clipboard: Debug {
+    /// The text in the clipboard, `None` if it is empty or cannot be read.
+    fn read(&mut self) -> Option<String>;
+
+    /// Replaces the content of the clipboard with `text`.
+    fn write(&mut self, text: &str) -> color_eyre::Result<()>;
+}
+
+/// Clipboard shared between widgets.
+pub type SharedClipboard = Rc<RefCell<dyn Clipboard>>;
+
+pub fn shared(clipboard: impl Clipboard + 'static) -> SharedClipboard {
+    Rc::new(RefCell::new(clipboard))
+}
+
+/// The system clipboard if the `system-clipboard` feature is enabled and a clipboard is
+/// available, otherwise [`Osc52Clipboard`].
+pub fn default_clipboard() -> SharedClipboard {
+    #[cfg(feature = "system-clipboard")]
+    if let Ok(clipboard) = SystemClipboard::new() {
+        return shared(clipboard);
+    }
+    shared(Osc52Clipboard::new())
+}
+
+/// Keeps the text in memory, for tests and applications that only copy between their own
+/// widgets.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryClipboard {
+    text: Option<String>,
+}
+
+impl MemoryClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn read(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn write(&mut self, text: &str) -> color_eyre::Result<()> {
+        self.text = Some(text.to_string());
+        Ok(())
+    }
+}
+
+/// Sets the clipboard of the terminal with the OSC 52 escape sequence.
+///
+/// The sequence travels through the terminal connection, so this works over SSH and inside
+/// tmux (with `set-clipboard` enabled) where no system clipboard is reachable. Terminals
+/// answer clipboard queries on stdin, where they would get mixed up with the input events, so
+/// [`Clipboard::read`] only returns the text last written by this application.
+#[derive(Debug)]
+pub struct Osc52Clipboard<W: Write = io::Stdout> {
+    writer: W,
+    tmux: bool,
+    last: Option<String>,
+}
+
+impl Default for Osc52Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Osc52Clipboard {
+    /// Writes to stdout, the sequence is wrapped for tmux if `$TMUX` is set.
+    pub fn new() -> Self {
+        Self::with_writer(io::stdout(), std::env::var_os("TMUX").is_some())
+    }
+}
+
+impl<W: Write> Osc52Clipboard<W> {
+    pub fn with_writer(writer: W, tmux: bool) -> Self {
+        Self {
+            writer,
+            tmux,
+            last: None,
+        }
+    }
+}
+
+impl<W: Write + Debug> Clipboard for Osc52Clipboard<W> {
+    fn read(&mut self) -> Option<String> {
+        self.last.clone()
+    }
+
+    fn write(&mut self, text: &str) -> color_eyre::Result<()> {
+        self.writer
+            .write_all(osc52_sequence(text, self.tmux).as_bytes())?;
+        self.writer.flush()?;
+        self.last = Some(text.to_string());
+        Ok(())
+    }
+}
+
+/// The escape sequence that sets the clipboard to `text`.
+///
+/// tmux only forwards sequences wrapped in a DCS passthrough, with every escape doubled.
+pub fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+/// The clipboard of the desktop environment.
+#[cfg(feature = "system-clipboard")]
+pub struct SystemClipboard {
+    clipboard: arboard::Clipboard,
+}
+
+#[cfg(feature = "system-clipboard")]
+impl SystemClipboard {
+    /// Fails if there is no clipboard, e.g. in a session without a display server.
+    pub fn new() -> color_eyre::Result<Self> {
+        Ok(Self {
+            clipboard: arboard::Clipboard::new()?,
+        })
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+impl Debug for SystemClipboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SystemClipboard")
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+impl Clipboard for SystemClipboard {
+    fn read(&mut self) -> Option<String> {
+        self.clipboard.get_text().ok()
+    }
+
+    fn write(&mut self, text: &str) -> color_eyre::Result<()> {
+        self.clipboard.set_text(text)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn osc52_sequences() {
+        let mut clipboard = Osc52Clipboard::with_writer(Vec::new(), false);
+        clipboard.write("hi").unwrap();
+        assert_eq!(clipboard.writer, b"\x1b]52;c;aGk=\x07");
+        assert_eq!(clipboard.read().as_deref(), Some("hi"));
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+}
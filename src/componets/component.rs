@@ -1,16 +1,33 @@
-use crate::widgets::Widget;
+use crate::componets::focus;
+use crate::componets::overlay::Overlay;
+use crate::componets::path::WidgetPath;
+use crate::componets::style::Style;
+use crate::widgets::{Widget, WidgetEvent};
+use ratatui::layout::Constraint;
 use std::cell::RefCell;
+use std::ops::Index;
 use std::rc::Rc;
 use std::slice::Iter;
 
-type WidgetType = Rc<RefCell<dyn Widget>>;
+pub type WidgetType = Rc<RefCell<dyn Widget>>;
 
 #[derive(Debug, Clone)]
 pub enum Component {
     Widget(WidgetType),
+    /// Children stacked vertically.
     ListView(Rc<[Component]>),
+    /// Children side by side.
+    RowView(Rc<[Component]>),
     GridView(Rc<[Rc<[Component]>]>),
-    Floating(WidgetType),
+    /// A child drawn on top of the tree, placed by its [`Overlay`].
+    Floating(Overlay, Rc<Component>),
+    /// Children placed by the flexbox [`Style`], which also sizes the node in a parent flex
+    /// node.
+    Flex(Style, Rc<[Component]>),
+    /// A child with the space it takes in its [`ListView`](Component::ListView),
+    /// [`RowView`](Component::RowView) or grid row, children without one fill the remaining
+    /// space evenly.
+    Sized(Constraint, Rc<Component>),
 }
 
 impl<T> From<&[T]> for Component
@@ -18,7 +35,7 @@ where
     T: Widget + 'static + Clone,
 {
     fn from(from: &[T]) -> Self {
-        let from_vec: Vec<Component> = from.to_vec().into_iter().map(|i| i.into()).collect();
+        let from_vec: Vec<Component> = from.iter().cloned().map(|i| i.into()).collect();
 
         let from_box = from_vec.into_boxed_slice();
         Self::ListView(Rc::from(from_box))
@@ -30,7 +47,7 @@ where
     T: Widget + 'static + Clone,
 {
     fn from(from: [T; N]) -> Self {
-        let from_vec: Vec<Component> = from.to_vec().into_iter().map(|i| i.into()).collect();
+        let from_vec: Vec<Component> = from.into_iter().map(|i| i.into()).collect();
 
         let from_box = from_vec.into_boxed_slice();
         Self::ListView(Rc::from(from_box))
@@ -48,31 +65,14 @@ where
 
 // Iterator that recursively traverses WidgetElement and yields Rc<RefCell<dyn Widget>>
 pub struct WidgetElementIter<'a> {
-    // Stack of slices iterators for recursive traversal
+    // Stack of slices iterators for recursive traversal, starting with the root
     stack: Vec<Iter<'a, Component>>,
-    // Current item if at leaf
-    current_item: Option<&'a Rc<RefCell<dyn Widget>>>,
 }
 
 impl<'a> WidgetElementIter<'a> {
     pub fn new(root: &'a Component) -> Self {
-        let mut stack = Vec::new();
-        let current_item = match root {
-            Component::Floating(item) | Component::Widget(item) => Some(item),
-            Component::ListView(collection) => {
-                stack.push(collection.iter());
-                None
-            }
-            Component::GridView(collection) => {
-                for iter in collection.iter() {
-                    stack.push(iter.iter());
-                }
-                None
-            }
-        };
         Self {
-            stack,
-            current_item,
+            stack: vec![std::slice::from_ref(root).iter()],
         }
     }
 }
@@ -81,26 +81,26 @@ impl<'a> Iterator for WidgetElementIter<'a> {
     type Item = &'a Rc<RefCell<dyn Widget>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // If currently at an item, return it once and clear
-        if let Some(item) = self.current_item.take() {
-            return Some(item);
-        }
-
-        // Otherwise, iterate through the stack of collections
+        // Iterate through the stack of collections
         while let Some(top_iter) = self.stack.last_mut() {
             if let Some(next_element) = top_iter.next() {
                 match next_element {
-                    Component::Floating(item) | Component::Widget(item) => {
+                    Component::Widget(item) => {
                         // Return the current item reference
                         return Some(item);
                     }
-                    Component::ListView(collection) => {
+                    Component::ListView(collection)
+                    | Component::RowView(collection)
+                    | Component::Flex(_, collection) => {
                         // Push the iterator of this collection onto stack
                         self.stack.push(collection.iter());
                     }
+                    Component::Sized(_, child) | Component::Floating(_, child) => {
+                        self.stack.push(std::slice::from_ref(&**child).iter());
+                    }
                     Component::GridView(collection) => {
-                        // Push the iterator of this collection onto stack
-                        for iter in collection.iter() {
+                        // Push the rows in reverse, so the first row is visited first
+                        for iter in collection.iter().rev() {
                             self.stack.push(iter.iter());
                         }
                     }
@@ -121,55 +121,109 @@ impl Component {
         WidgetElementIter::new(self)
     }
 
+    /// The first widget that has focus, inside the topmost
+    /// [capturing](crate::componets::overlay::Overlay::capture) overlay if there is one.
+    pub fn focused(&self) -> Option<&WidgetType> {
+        focus::trap(self)
+            .unwrap_or(self)
+            .iter()
+            .find(|widget| widget.borrow().is_focused())
+    }
+
+    /// Delivers pasted text to the focused widget.
+    pub fn handle_paste(&self, text: &str) -> Option<WidgetEvent> {
+        self.focused()?.borrow_mut().handle_paste(text)
+    }
+
+    /// Wraps the component in [`Component::Sized`].
+    pub fn sized(self, constraint: Constraint) -> Self {
+        Self::Sized(constraint, Rc::new(self))
+    }
+
+    /// The component without its [`Sized`](Component::Sized) wrappers.
+    pub fn inner(&self) -> &Component {
+        match self {
+            Component::Sized(_, child) => child.inner(),
+            _ => self,
+        }
+    }
+
+    /// The component without its [`Sized`](Component::Sized) and
+    /// [`Floating`](Component::Floating) wrappers.
+    pub fn content(&self) -> &Component {
+        match self {
+            Component::Sized(_, child) | Component::Floating(_, child) => child.content(),
+            _ => self,
+        }
+    }
+
     pub fn num_rows(&self) -> usize {
         match self {
-            Component::Item(i) => i.borrow().get_len(),
-            Component::Collection(c) => c.len(),
-            Component::CollectionWithLongElement(c, long_item) => {
-                c.len() + c[*long_item].num_rows()
-            }
+            Component::Widget(i) => i.borrow().get_len(),
+            Component::ListView(c) | Component::RowView(c) | Component::Flex(_, c) => c.len(),
+            Component::GridView(rows) => rows.len(),
+            Component::Sized(_, child) | Component::Floating(_, child) => child.num_rows(),
         }
     }
     pub fn num_col(&self, row: usize) -> usize {
         match self {
-            Component::Item(i) => i.borrow().get_len(),
-            Component::Collection(c) => c[row].num_rows(),
-            Component::CollectionWithLongElement(c, long_item) => {
-                c.len() + c[*long_item].num_rows()
+            Component::Widget(i) => i.borrow().get_len(),
+            Component::ListView(c) | Component::RowView(c) | Component::Flex(_, c) => {
+                c[row].num_rows()
             }
+            Component::GridView(rows) => rows[row].len(),
+            Component::Sized(_, child) | Component::Floating(_, child) => child.num_col(row),
         }
     }
 
+    /// The widget at the path given by `indecies`, see [`WidgetPath`].
     pub fn get_widget(&self, indecies: &[usize]) -> Option<Rc<RefCell<dyn Widget>>> {
-        let mut current_item: Self = self.clone();
-        for index in indecies {
-            current_item = match current_item {
-                Self::Item(item) => Self::Item(item.clone()),
-                Self::Collection(collection) => collection[*index].clone(),
-                Self::CollectionWithLongElement(collection, _) => collection[*index].clone(),
-            };
-            if let Self::Item(item) = current_item {
-                return Some(item);
-            }
+        match self.get(&WidgetPath::from(indecies))?.content() {
+            Component::Widget(item) => Some(item.clone()),
+            _ => None,
         }
-        None
     }
 
     pub fn get_item_2d(&self, row: usize, column: usize) -> Option<Rc<RefCell<dyn Widget>>> {
         match self {
-            Component::CollectionWithLongElement(c, _) | Component::Collection(c) => {
-                match c[row].clone() {
-                    Component::CollectionWithLongElement(c, _) | Component::Collection(c) => {
-                        match c[column].clone() {
-                            Component::CollectionWithLongElement(_, _)
-                            | Component::Collection(_) => None,
-                            Component::Item(item) => Some(item.clone()),
-                        }
-                    }
-                    Component::Item(item) => Some(item.clone()),
+            Component::GridView(_) => self.get_widget(&[row, column]),
+            Component::ListView(c) | Component::RowView(c) | Component::Flex(_, c) => {
+                match c.get(row)?.content() {
+                    Component::Widget(item) => Some(item.clone()),
+                    row => row.get_widget(&[column]),
                 }
             }
-            Component::Item(item) => Some(item.clone()),
+            Component::Widget(item) => Some(item.clone()),
+            Component::Sized(_, child) | Component::Floating(_, child) => {
+                child.get_item_2d(row, column)
+            }
+        }
+    }
+}
+
+impl Index<usize> for Component {
+    type Output = Component;
+
+    /// The child at `index` of a [`Component::ListView`], [`Component::RowView`] or
+    /// [`Component::Flex`].
+    ///
+    /// Panics for other components, index a [`Component::GridView`] with `(row, column)`.
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.content() {
+            Component::ListView(c) | Component::RowView(c) | Component::Flex(_, c) => &c[index],
+            _ => panic!("only a ListView, RowView or Flex can be indexed with a single index"),
+        }
+    }
+}
+
+impl Index<(usize, usize)> for Component {
+    type Output = Component;
+
+    /// The cell at `(row, column)` of a [`Component::GridView`], panics for other components.
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        match self.content() {
+            Component::GridView(rows) => &rows[row][column],
+            _ => panic!("only a GridView can be indexed with (row, column)"),
         }
     }
 }
@@ -194,16 +248,26 @@ mod test {
     use super::*;
     #[test]
     fn test() {
-        let a = crate::widgets::Button::new("abc", 'c', "abc");
-        let b = crate::widgets::Button::new("abc", 'c', "abc");
-        let c = crate::widgets::Button::new("abc", 'c', "abc");
-        let d = crate::widgets::Button::new("abc", 'c', "abc");
+        let a = crate::widgets::Button::new("abc", 'c');
+        let b = crate::widgets::Button::new("abc", 'c');
+        let c = crate::widgets::Button::new("abc", 'c');
+        let d = crate::widgets::Button::new("abc", 'c');
         let y = widget_element!([a, [b, c], d]);
-        assert!(matches!(y, Component::Collection(_)));
-        assert!(matches!(y[0], Component::Item(_)));
-        assert!(matches!(y[1], Component::Collection(_)));
-        assert!(matches!(y[1][0], Component::Item(_)));
-        assert!(matches!(y[1][1], Component::Item(_)));
-        assert!(matches!(y[2], Component::Item(_)));
+        assert!(matches!(y, Component::ListView(_)));
+        assert!(matches!(y[0], Component::Widget(_)));
+        assert!(matches!(y[1], Component::ListView(_)));
+        assert!(matches!(y[1][0], Component::Widget(_)));
+        assert!(matches!(y[1][1], Component::Widget(_)));
+        assert!(matches!(y[2], Component::Widget(_)));
+    }
+
+    #[test]
+    fn sized_lookups() {
+        let a = crate::widgets::Button::new("abc", 'a');
+        let b = crate::widgets::Button::new("abc", 'b');
+        let y = widget_element!([a, b]).sized(Constraint::Length(3));
+        assert_eq!(y.num_rows(), 2);
+        assert!(y.get_item_2d(1, 0).is_some());
+        assert!(y.get_item_2d(2, 0).is_none());
     }
 }
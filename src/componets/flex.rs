@@ -0,0 +1,240 @@
+use crate::componets::component::Component;
+use crate::componets::style::Style;
+use ratatui::layout::Rect;
+use std::rc::Rc;
+
+impl Component {
+    /// Wraps the component in a [`Flex`](Component::Flex) node, to size it with `style` inside
+    /// another flex node.
+    pub fn styled(self, style: Style) -> Self {
+        Component::Flex(style, Rc::from([self]))
+    }
+}
+
+/// The area inside the padding of `style`.
+#[cfg(not(feature = "flex"))]
+fn content_area(style: &Style, area: Rect) -> Rect {
+    let padding = style.padding;
+    let x = area.x.saturating_add(padding.left).min(area.right());
+    let y = area.y.saturating_add(padding.top).min(area.bottom());
+    Rect::new(
+        x,
+        y,
+        area.right().saturating_sub(padding.right).saturating_sub(x),
+        area.bottom()
+            .saturating_sub(padding.bottom)
+            .saturating_sub(y),
+    )
+}
+
+/// Splits `area` between the `children` of a flex node by their constraints.
+#[cfg(not(feature = "flex"))]
+pub(crate) fn layout(style: &Style, children: &[&Component], area: Rect) -> Vec<Rect> {
+    use crate::componets::style::Justify;
+    use ratatui::layout::{Flex, Layout};
+
+    let flex = match style.justify {
+        Justify::Start => Flex::Start,
+        Justify::End => Flex::End,
+        Justify::Center => Flex::Center,
+        Justify::SpaceBetween => Flex::SpaceBetween,
+        Justify::SpaceAround | Justify::SpaceEvenly => Flex::SpaceAround,
+    };
+    Layout::new(
+        style.direction,
+        children.iter().map(|child| child.constraint()),
+    )
+    .spacing(style.gap)
+    .flex(flex)
+    .split(content_area(style, area))
+    .to_vec()
+}
+
+/// Lays out the `children` of a flex node with the flexbox algorithm.
+///
+/// The whole subtree of nested flex nodes is solved at once, so a node without a size can take
+/// the size of its content. The nested nodes are solved again when they are drawn, with the
+/// area they got here.
+#[cfg(feature = "flex")]
+pub(crate) fn layout(style: &Style, children: &[&Component], area: Rect) -> Vec<Rect> {
+    use taffy::prelude::{NodeId, Size, TaffyMaxContent, TaffyTree, length};
+
+    let mut tree: TaffyTree<()> = TaffyTree::new();
+    let nodes: Vec<NodeId> = children
+        .iter()
+        .map(|child| engine::node(&mut tree, child, style.direction))
+        .collect();
+    let mut root_style = engine::container(style);
+    root_style.size = Size {
+        width: length(area.width as f32),
+        height: length(area.height as f32),
+    };
+    let root = tree
+        .new_with_children(root_style, &nodes)
+        .expect("failed to build the flex tree");
+    if tree.compute_layout(root, Size::MAX_CONTENT).is_err() {
+        return vec![Rect::default(); nodes.len()];
+    }
+    nodes
+        .iter()
+        .map(|node| {
+            let Ok(layout) = tree.layout(*node) else {
+                return Rect::default();
+            };
+            let cell = |value: f32| value.round().clamp(0.0, u16::MAX as f32) as u16;
+            Rect::new(
+                area.x.saturating_add(cell(layout.location.x)),
+                area.y.saturating_add(cell(layout.location.y)),
+                cell(layout.size.width),
+                cell(layout.size.height),
+            )
+            .intersection(area)
+        })
+        .collect()
+}
+
+#[cfg(feature = "flex")]
+mod engine {
+    use crate::componets::component::Component;
+    use crate::componets::style::{Align, Justify, Style};
+    use ratatui::layout::{Constraint, Direction};
+    use taffy::prelude::*;
+
+    /// Adds `child` of a node with `direction` to `tree`, with all its nested flex nodes.
+    pub(super) fn node(
+        tree: &mut TaffyTree<()>,
+        child: &Component,
+        direction: Direction,
+    ) -> NodeId {
+        let node = match child {
+            Component::Flex(style, children) => {
+                let nodes: Vec<NodeId> = children
+                    .iter()
+                    .filter(|child| !matches!(child.inner(), Component::Floating(..)))
+                    .map(|child| node(tree, child, style.direction))
+                    .collect();
+                tree.new_with_children(container(style), &nodes)
+            }
+            Component::Sized(constraint, _) => tree.new_leaf(constrained(*constraint, direction)),
+            _ => tree.new_leaf(container(&Style::default())),
+        };
+        // building the tree only fails when running out of memory
+        node.expect("failed to build the flex tree")
+    }
+
+    pub(super) fn container(style: &Style) -> taffy::Style {
+        let padding = style.padding;
+        let cells = |value: u16| length(value as f32);
+        let optional = |value: Option<u16>| value.map_or(auto(), cells);
+        taffy::Style {
+            display: Display::Flex,
+            flex_direction: match style.direction {
+                Direction::Horizontal => FlexDirection::Row,
+                Direction::Vertical => FlexDirection::Column,
+            },
+            flex_wrap: if style.wrap {
+                FlexWrap::Wrap
+            } else {
+                FlexWrap::NoWrap
+            },
+            align_items: Some(match style.align {
+                Align::Start => AlignItems::Start,
+                Align::End => AlignItems::End,
+                Align::Center => AlignItems::Center,
+                Align::Stretch => AlignItems::Stretch,
+            }),
+            justify_content: Some(match style.justify {
+                Justify::Start => JustifyContent::Start,
+                Justify::End => JustifyContent::End,
+                Justify::Center => JustifyContent::Center,
+                Justify::SpaceBetween => JustifyContent::SpaceBetween,
+                Justify::SpaceAround => JustifyContent::SpaceAround,
+                Justify::SpaceEvenly => JustifyContent::SpaceEvenly,
+            }),
+            gap: Size {
+                width: length(style.gap as f32),
+                height: length(style.gap as f32),
+            },
+            padding: Rect {
+                left: length(padding.left as f32),
+                right: length(padding.right as f32),
+                top: length(padding.top as f32),
+                bottom: length(padding.bottom as f32),
+            },
+            flex_grow: style.grow,
+            flex_shrink: style.shrink,
+            flex_basis: optional(style.basis),
+            min_size: Size {
+                width: optional(style.min_width),
+                height: optional(style.min_height),
+            },
+            max_size: Size {
+                width: optional(style.max_width),
+                height: optional(style.max_height),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Item style of a [`Component::Sized`] child, the constraint applies to the main axis of
+    /// the parent.
+    fn constrained(constraint: Constraint, direction: Direction) -> taffy::Style {
+        let mut item = container(&Style::default());
+        let main = |size: &mut Size<Dimension>, value: Dimension| match direction {
+            Direction::Horizontal => size.width = value,
+            Direction::Vertical => size.height = value,
+        };
+        match constraint {
+            Constraint::Length(cells) => {
+                item.flex_basis = length(cells as f32);
+                item.flex_grow = 0.0;
+                item.flex_shrink = 0.0;
+            }
+            Constraint::Min(cells) => main(&mut item.min_size, length(cells as f32)),
+            Constraint::Max(cells) => main(&mut item.max_size, length(cells as f32)),
+            Constraint::Fill(weight) => item.flex_grow = weight as f32,
+            Constraint::Percentage(share) => {
+                item.flex_basis = percent(share as f32 / 100.0);
+                item.flex_grow = 0.0;
+            }
+            Constraint::Ratio(numerator, denominator) => {
+                item.flex_basis = percent(numerator as f32 / denominator.max(1) as f32);
+                item.flex_grow = 0.0;
+            }
+        }
+        item
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::componets::component::WidgetType;
+    use crate::componets::mouse::HitMap;
+    use crate::widgets::{Button, Widget};
+    use ratatui::buffer::Buffer;
+    use ratatui::widgets::Padding;
+
+    #[test]
+    fn flex_sizes() {
+        let buttons: Vec<WidgetType> = (0..3)
+            .map(|i| Button::new(&i.to_string(), 'x').boxed() as WidgetType)
+            .collect();
+        let root = Component::Flex(
+            Style::row().gap(1).padding(Padding::uniform(1)),
+            Rc::from([
+                Component::Widget(buttons[0].clone()).styled(Style::row().fixed(4)),
+                Component::Widget(buttons[1].clone()).styled(Style::row().grow(1.0)),
+                Component::Widget(buttons[2].clone()).styled(Style::row().grow(2.0)),
+            ]),
+        );
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buf = Buffer::empty(area);
+        let mut hits = HitMap::new();
+        root.render(area, &mut buf, &mut hits);
+        // 18 columns inside the padding, 2 gaps and 4 fixed leave 12 for the rest
+        assert_eq!(hits.area_of(&buttons[0]), Some(Rect::new(1, 1, 4, 3)));
+        assert_eq!(hits.area_of(&buttons[1]), Some(Rect::new(6, 1, 4, 3)));
+        assert_eq!(hits.area_of(&buttons[2]), Some(Rect::new(11, 1, 8, 3)));
+    }
+}
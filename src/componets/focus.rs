@@ -0,0 +1,391 @@
+use crate::componets::component::{Component, WidgetType};
+use crate::componets::path::WidgetRef;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::rc::Rc;
+
+/// Where to move the focus to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusDirection {
+    /// The next widget in document order.
+    Next,
+    /// The previous widget in document order.
+    Prev,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl FocusDirection {
+    /// The direction bound to `key`: Tab, Shift-Tab and the arrow keys.
+    pub fn from_key(key: KeyEvent) -> Option<Self> {
+        if !key.is_press() {
+            return None;
+        }
+        let modifiers = key.modifiers - KeyModifiers::SHIFT;
+        if !modifiers.is_empty() {
+            return None;
+        }
+        match key.code {
+            KeyCode::Tab if key.modifiers.contains(KeyModifiers::SHIFT) => Some(Self::Prev),
+            KeyCode::Tab => Some(Self::Next),
+            KeyCode::BackTab => Some(Self::Prev),
+            KeyCode::Up => Some(Self::Up),
+            KeyCode::Down => Some(Self::Down),
+            KeyCode::Left => Some(Self::Left),
+            KeyCode::Right => Some(Self::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Moves the focus between the widgets of a [`Component`] tree.
+///
+/// Tab and Shift-Tab walk the tree in document order, the arrow keys move between the cells of
+/// the [`GridView`](Component::GridView) around the focused widget. Widgets that are not
+/// [focusable](crate::widgets::Widget::focusable) are skipped. While the tree contains a
+/// [`Floating`](Component::Floating) component whose [`Overlay`] captures the input, the focus
+/// stays inside the topmost one of them.
+///
+/// [`Overlay`]: crate::componets::overlay::Overlay
+#[derive(Debug, Clone)]
+pub struct FocusManager {
+    wrap: bool,
+}
+
+impl Default for FocusManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FocusManager {
+    pub fn new() -> Self {
+        Self { wrap: true }
+    }
+
+    /// Whether Tab on the last widget moves to the first one, enabled by default.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Moves the focus if `key` is one of the navigation keys, returns the newly focused widget.
+    ///
+    /// Keys the focused widget [wants](crate::widgets::Widget::wants_key) are left to it, and
+    /// arrow keys are only used up if there is a cell to move to, so call this before handing
+    /// the key to the focused widget.
+    pub fn handle_key_event(&self, root: &Component, key: KeyEvent) -> Option<WidgetType> {
+        let direction = FocusDirection::from_key(key)?;
+        if root
+            .focused()
+            .is_some_and(|widget| widget.borrow().wants_key(key))
+        {
+            return None;
+        }
+        self.move_focus(root, direction)
+    }
+
+    /// Moves the focus in `direction`, returns the newly focused widget.
+    pub fn move_focus(&self, root: &Component, direction: FocusDirection) -> Option<WidgetType> {
+        let order = focus_order(root);
+        let current = order.iter().position(|widget| widget.borrow().is_focused());
+        let target = match (direction, current) {
+            (_, None) => order.first().cloned(),
+            (FocusDirection::Next, Some(index)) => match order.get(index + 1) {
+                Some(widget) => Some(widget.clone()),
+                None if self.wrap => order.first().cloned(),
+                None => None,
+            },
+            (FocusDirection::Prev, Some(index)) => match index.checked_sub(1) {
+                Some(index) => order.get(index).cloned(),
+                None if self.wrap => order.last().cloned(),
+                None => None,
+            },
+            _ => match spatial(trap(root).unwrap_or(root), direction) {
+                Move::Moved(widget) => Some(widget),
+                Move::Missing | Move::Blocked => None,
+            },
+        }?;
+        focus(root, &target);
+        Some(target)
+    }
+
+    /// Focuses the widget `target` refers to, e.g. from [`AppEvent::FocusItem`].
+    ///
+    /// [`AppEvent::FocusItem`]: crate::events::AppEvent::FocusItem
+    pub fn focus_ref(&self, root: &Component, target: &WidgetRef) -> Option<WidgetType> {
+        let target = target.resolve(root)?;
+        focus(root, &target);
+        Some(target)
+    }
+
+    /// Focuses the widget at `index` in document order, counting only focusable widgets.
+    pub fn focus_index(&self, root: &Component, index: usize) -> Option<WidgetType> {
+        let target = focus_order(root).get(index)?.clone();
+        focus(root, &target);
+        Some(target)
+    }
+}
+
+/// Focuses `widget` and unfocuses every other widget in `root`.
+pub fn focus(root: &Component, widget: &WidgetType) {
+    for other in root.iter() {
+        if Rc::ptr_eq(other, widget) {
+            other.borrow_mut().focus();
+        } else if other.borrow().is_focused() {
+            other.borrow_mut().unfocus();
+        }
+    }
+}
+
+/// The focusable widgets in the order Tab visits them.
+fn focus_order(root: &Component) -> Vec<WidgetType> {
+    trap(root)
+        .unwrap_or(root)
+        .iter()
+        .filter(|widget| widget.borrow().focusable())
+        .cloned()
+        .collect()
+}
+
+/// The content of the topmost overlay in `root` that captures the input, it keeps the focus
+/// while it is open.
+pub(crate) fn trap(root: &Component) -> Option<&Component> {
+    let mut overlays = Vec::new();
+    capturing(root, &mut overlays);
+    // the last one of the highest overlays is drawn on top
+    overlays
+        .into_iter()
+        .max_by_key(|(z, _)| *z)
+        .map(|(_, content)| content)
+}
+
+/// Collects the content of the capturing overlays in `component` with their z, in drawing order.
+fn capturing<'a>(component: &'a Component, overlays: &mut Vec<(i32, &'a Component)>) {
+    match component {
+        Component::Widget(_) => {}
+        Component::Floating(overlay, child) => {
+            if overlay.capture {
+                overlays.push((overlay.z, child));
+            }
+            capturing(child, overlays);
+        }
+        Component::Sized(_, child) => capturing(child, overlays),
+        Component::ListView(children)
+        | Component::RowView(children)
+        | Component::Flex(_, children) => {
+            children.iter().for_each(|child| capturing(child, overlays))
+        }
+        Component::GridView(rows) => rows
+            .iter()
+            .flat_map(|row| row.iter())
+            .for_each(|child| capturing(child, overlays)),
+    }
+}
+
+fn first_focusable(component: &Component) -> Option<WidgetType> {
+    component
+        .iter()
+        .find(|widget| widget.borrow().focusable())
+        .cloned()
+}
+
+/// Result of a spatial move inside a part of the tree.
+enum Move {
+    /// The focused widget is not in this part.
+    Missing,
+    /// The focused widget is in this part, but there is nothing in that direction.
+    Blocked,
+    Moved(WidgetType),
+}
+
+/// Moves from the focused widget to the neighbouring cell of the innermost grid around it that
+/// has one.
+fn spatial(component: &Component, direction: FocusDirection) -> Move {
+    match component {
+        Component::Widget(widget) => {
+            if widget.borrow().is_focused() {
+                Move::Blocked
+            } else {
+                Move::Missing
+            }
+        }
+        Component::Sized(_, child) | Component::Floating(_, child) => spatial(child, direction),
+        Component::ListView(children)
+        | Component::RowView(children)
+        | Component::Flex(_, children) => children
+            .iter()
+            .map(|child| spatial(child, direction))
+            .find(|result| !matches!(result, Move::Missing))
+            .unwrap_or(Move::Missing),
+        Component::GridView(rows) => {
+            for (row, cells) in rows.iter().enumerate() {
+                for (column, cell) in cells.iter().enumerate() {
+                    match spatial(cell, direction) {
+                        Move::Missing => continue,
+                        Move::Moved(widget) => return Move::Moved(widget),
+                        Move::Blocked => {
+                            return neighbour(rows, row, column, direction)
+                                .map_or(Move::Blocked, Move::Moved);
+                        }
+                    }
+                }
+            }
+            Move::Missing
+        }
+    }
+}
+
+/// The first focusable widget in the closest cell from `row`/`column` in `direction`.
+fn neighbour(
+    rows: &[Rc<[Component]>],
+    mut row: usize,
+    mut column: usize,
+    direction: FocusDirection,
+) -> Option<WidgetType> {
+    loop {
+        match direction {
+            FocusDirection::Left => column = column.checked_sub(1)?,
+            FocusDirection::Right => column += 1,
+            FocusDirection::Up => row = row.checked_sub(1)?,
+            FocusDirection::Down => row += 1,
+            FocusDirection::Next | FocusDirection::Prev => return None,
+        }
+        let cells = rows.get(row)?;
+        if cells.is_empty() {
+            continue;
+        }
+        // rows of different length: go to the closest cell of the shorter row
+        let cell = match direction {
+            FocusDirection::Up | FocusDirection::Down => &cells[column.min(cells.len() - 1)],
+            _ => cells.get(column)?,
+        };
+        if let Some(widget) = first_focusable(cell) {
+            return Some(widget);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::componets::overlay::Overlay;
+    use crate::widgets::{Button, InputWidget, Widget};
+    use ratatui::{
+        buffer::Buffer,
+        layout::{Constraint, Rect},
+    };
+
+    #[derive(Debug)]
+    struct Label;
+
+    impl Widget for Label {
+        fn focusable(&self) -> bool {
+            false
+        }
+        fn clear(&mut self, _: bool) {}
+        fn draw(&self, _: Rect, _: &mut Buffer, _: &mut Option<u16>) {}
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn tab_and_arrows() {
+        let buttons: Vec<WidgetType> = (0..5)
+            .map(|i| Button::new(&i.to_string(), 'x').boxed() as WidgetType)
+            .collect();
+        let cell = |i: usize| Component::Widget(buttons[i].clone());
+        // 0 1
+        // 2 label
+        // 3
+        let grid = Component::GridView(Rc::from([
+            Rc::from([cell(0), cell(1)]),
+            Rc::from([cell(2), Component::from(Label)]),
+            Rc::from([cell(3)]),
+        ]));
+        let root = Component::ListView(Rc::from([grid, cell(4)]));
+        let manager = FocusManager::new();
+        let focused = |target: Option<WidgetType>| {
+            let target = target.unwrap();
+            buttons.iter().position(|b| Rc::ptr_eq(b, &target)).unwrap()
+        };
+
+        assert_eq!(
+            focused(manager.handle_key_event(&root, key(KeyCode::Tab))),
+            0
+        );
+        assert_eq!(
+            focused(manager.handle_key_event(&root, key(KeyCode::Right))),
+            1
+        );
+        // the label below is skipped, the last row is shorter
+        assert_eq!(
+            focused(manager.handle_key_event(&root, key(KeyCode::Down))),
+            3
+        );
+        assert_eq!(
+            focused(manager.handle_key_event(&root, key(KeyCode::Up))),
+            2
+        );
+        assert!(
+            manager
+                .handle_key_event(&root, key(KeyCode::Left))
+                .is_none()
+        );
+        assert_eq!(
+            focused(manager.handle_key_event(&root, key(KeyCode::Tab))),
+            3
+        );
+        assert_eq!(
+            focused(manager.handle_key_event(&root, key(KeyCode::Tab))),
+            4
+        );
+        assert_eq!(
+            focused(manager.handle_key_event(&root, key(KeyCode::Tab))),
+            0
+        );
+        assert_eq!(
+            focused(manager.handle_key_event(&root, key(KeyCode::BackTab))),
+            4
+        );
+        assert_eq!(root.iter().filter(|w| w.borrow().is_focused()).count(), 1);
+
+        // an input keeps Left and Right for its cursor, Tab still moves on
+        let input = InputWidget::new("name").boxed();
+        let row = Component::GridView(Rc::from([Rc::from([
+            Component::Widget(input.clone()),
+            cell(4),
+        ])]));
+        input.borrow_mut().focus();
+        buttons[4].borrow_mut().unfocus();
+        assert!(
+            manager
+                .handle_key_event(&row, key(KeyCode::Right))
+                .is_none()
+        );
+        assert!(input.borrow().is_focused());
+        assert_eq!(
+            focused(manager.handle_key_event(&row, key(KeyCode::Tab))),
+            4
+        );
+
+        let dialog = Button::new("dialog", 'd').boxed();
+        let popup = Component::Widget(dialog.clone()).floating(Overlay::modal(
+            Constraint::Length(10),
+            Constraint::Length(3),
+        ));
+        let root = Component::ListView(Rc::from([root, popup]));
+        let target = manager.handle_key_event(&root, key(KeyCode::Tab)).unwrap();
+        assert!(Rc::ptr_eq(&target, &(dialog.clone() as WidgetType)));
+        let target = manager.handle_key_event(&root, key(KeyCode::Tab)).unwrap();
+        assert!(Rc::ptr_eq(&target, &(dialog as WidgetType)));
+        assert!(
+            manager
+                .handle_key_event(&root, key(KeyCode::Down))
+                .is_none()
+        );
+    }
+}
@@ -1,5 +1,269 @@
-use crate::componets;
+use crate::componets::component::{Component, WidgetType};
+use crate::componets::focus;
+use crate::componets::mouse::HitMap;
+use crate::events::AppEvent;
+use color_eyre::eyre::eyre;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
 
+type Hook = Box<dyn FnMut(&Component)>;
+
+/// A screen of the application: a title and the root [`Component`] that fills the terminal.
+///
+/// The hooks are called by the [`ScreenStack`] with the root of the screen:
+/// - `on_enter` when the screen is shown for the first time,
+/// - `on_suspend` when another screen is pushed on top of it,
+/// - `on_resume` when it is uncovered again,
+/// - `on_leave` when it is closed.
+///
+/// The focused widget is remembered while the screen is covered and focused again when it is
+/// uncovered.
 pub struct Frame {
-    widgets: componets::component::Component,
+    title: String,
+    widgets: Component,
+    /// Widget that had focus when the screen was covered.
+    focus: Option<WidgetType>,
+    on_enter: Option<Hook>,
+    on_leave: Option<Hook>,
+    on_suspend: Option<Hook>,
+    on_resume: Option<Hook>,
+}
+
+impl Frame {
+    pub fn new(title: &str, widgets: impl Into<Component>) -> Self {
+        Self {
+            title: String::from(title),
+            widgets: widgets.into(),
+            focus: None,
+            on_enter: None,
+            on_leave: None,
+            on_suspend: None,
+            on_resume: None,
+        }
+    }
+
+    pub fn on_enter(mut self, hook: impl FnMut(&Component) + 'static) -> Self {
+        self.on_enter = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_leave(mut self, hook: impl FnMut(&Component) + 'static) -> Self {
+        self.on_leave = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_suspend(mut self, hook: impl FnMut(&Component) + 'static) -> Self {
+        self.on_suspend = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_resume(mut self, hook: impl FnMut(&Component) + 'static) -> Self {
+        self.on_resume = Some(Box::new(hook));
+        self
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn widgets(&self) -> &Component {
+        &self.widgets
+    }
+
+    /// Draws the root component, see [`Component::render`].
+    pub fn render(&self, area: Rect, buf: &mut Buffer, hits: &mut HitMap) -> Option<Position> {
+        self.widgets.render(area, buf, hits)
+    }
+
+    fn enter(&mut self) {
+        Self::call(&mut self.on_enter, &self.widgets);
+    }
+
+    fn leave(&mut self) {
+        Self::call(&mut self.on_leave, &self.widgets);
+    }
+
+    /// Remembers and clears the focus, so the covered screen does not react to input.
+    fn suspend(&mut self) {
+        self.focus = self.widgets.focused().cloned();
+        if let Some(widget) = self.focus.as_ref() {
+            widget.borrow_mut().unfocus();
+        }
+        Self::call(&mut self.on_suspend, &self.widgets);
+    }
+
+    fn resume(&mut self) {
+        if let Some(widget) = self.focus.take() {
+            focus::focus(&self.widgets, &widget);
+        }
+        Self::call(&mut self.on_resume, &self.widgets);
+    }
+
+    fn call(hook: &mut Option<Hook>, widgets: &Component) {
+        if let Some(hook) = hook.as_mut() {
+            hook(widgets);
+        }
+    }
+}
+
+impl Debug for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Frame")
+            .field("title", &self.title)
+            .field("widgets", &self.widgets)
+            .field("focus", &self.focus)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The open screens, the last one is shown.
+///
+/// Screens are opened by name through [`AppEvent::PushScreen`] and [`AppEvent::ReplaceScreen`],
+/// [`ScreenStack::register`] tells the stack how to build them.
+#[derive(Default)]
+pub struct ScreenStack {
+    screens: Vec<Frame>,
+    factories: HashMap<String, Box<dyn Fn() -> Frame>>,
+}
+
+impl ScreenStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the screen `name`, `factory` builds a new instance every time it is opened.
+    pub fn register(&mut self, name: &str, factory: impl Fn() -> Frame + 'static) {
+        self.factories.insert(String::from(name), Box::new(factory));
+    }
+
+    /// Builds the screen registered as `name`.
+    pub fn build(&self, name: &str) -> color_eyre::Result<Frame> {
+        let factory = self
+            .factories
+            .get(name)
+            .ok_or_else(|| eyre!("no screen registered as {name:?}"))?;
+        Ok(factory())
+    }
+
+    /// Shows `screen` on top of the current one.
+    pub fn push(&mut self, mut screen: Frame) {
+        if let Some(top) = self.screens.last_mut() {
+            top.suspend();
+        }
+        screen.enter();
+        self.screens.push(screen);
+    }
+
+    /// Closes the current screen and uncovers the one below.
+    pub fn pop(&mut self) -> Option<Frame> {
+        let mut screen = self.screens.pop()?;
+        screen.leave();
+        if let Some(top) = self.screens.last_mut() {
+            top.resume();
+        }
+        Some(screen)
+    }
+
+    /// Closes the current screen and shows `screen` instead.
+    pub fn replace(&mut self, mut screen: Frame) -> Option<Frame> {
+        let previous = self.screens.pop().map(|mut previous| {
+            previous.leave();
+            previous
+        });
+        screen.enter();
+        self.screens.push(screen);
+        previous
+    }
+
+    /// The screen that is shown.
+    pub fn top(&self) -> Option<&Frame> {
+        self.screens.last()
+    }
+
+    pub fn top_mut(&mut self) -> Option<&mut Frame> {
+        self.screens.last_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.screens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.screens.is_empty()
+    }
+
+    /// Handles the screen events, returns whether `event` was one of them.
+    ///
+    /// Fails if the event names a screen that was not registered.
+    pub fn handle_app_event(&mut self, event: &AppEvent) -> color_eyre::Result<bool> {
+        match event {
+            AppEvent::PushScreen(name) => self.push(self.build(name)?),
+            AppEvent::ReplaceScreen(name) => {
+                self.replace(self.build(name)?);
+            }
+            AppEvent::PopScreen => {
+                self.pop();
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+impl Debug for ScreenStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScreenStack")
+            .field("screens", &self.screens)
+            .field("factories", &self.factories.keys())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widgets::{Button, InputWidget, Widget};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn stack_keeps_focus() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let name = InputWidget::new("name").boxed();
+        name.borrow_mut().focus();
+        let hook = |event: &'static str| {
+            let log = log.clone();
+            move |_: &Component| log.borrow_mut().push(event)
+        };
+        let mut screens = ScreenStack::new();
+        screens.push(
+            Frame::new("main", Component::Widget(name.clone()))
+                .on_enter(hook("enter"))
+                .on_suspend(hook("suspend"))
+                .on_resume(hook("resume")),
+        );
+        screens.register("help", || Frame::new("help", Button::new("close", 'c')));
+
+        assert!(
+            screens
+                .handle_app_event(&AppEvent::PushScreen("help".into()))
+                .unwrap()
+        );
+        assert_eq!(screens.top().unwrap().title(), "help");
+        assert!(!name.borrow().is_focused());
+        assert!(screens.handle_app_event(&AppEvent::PopScreen).unwrap());
+        assert_eq!(screens.top().unwrap().title(), "main");
+        assert!(name.borrow().is_focused());
+        assert_eq!(*log.borrow(), ["enter", "suspend", "resume"]);
+
+        assert!(
+            screens
+                .handle_app_event(&AppEvent::ReplaceScreen("missing".into()))
+                .is_err()
+        );
+        assert!(!screens.handle_app_event(&AppEvent::Quit).unwrap());
+        assert_eq!(screens.len(), 1);
+    }
 }
@@ -1,24 +1,12 @@
 pub mod component;
+pub mod flex;
+pub mod focus;
 pub mod frame;
-
-#[derive(Debug)]
-pub struct Layout {
-    x: usize,
-    y: usize,
-    w: usize,
-    h: usize,
-}
-
-impl Layout {
-    pub fn new(x: usize, y: usize, w: usize, h: usize) -> Self {
-        Self { x, y, w, h }
-    }
-
-    pub fn get_possition(&self) -> (usize, usize) {
-        (self.x, self.y)
-    }
-
-    pub fn get_size(&self) -> (usize, usize) {
-        (self.w, self.h)
-    }
-}
+pub mod mouse;
+pub mod overlay;
+pub mod path;
+pub mod render;
+pub mod style;
+pub mod toast;
+
+pub use style::Style;
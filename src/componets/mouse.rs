@@ -0,0 +1,164 @@
+use crate::componets::component::{Component, WidgetType};
+use crate::componets::focus;
+use crate::widgets::WidgetEvent;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use std::rc::Rc;
+
+/// Areas the widgets of a [`Component`] tree were drawn to in the last frame.
+///
+/// Fill it while rendering, either with [`HitMap::insert`] or by drawing through
+/// [`HitMap::draw`], and clear it at the start of every frame.
+#[derive(Debug, Default, Clone)]
+pub struct HitMap {
+    /// In drawing order, later entries are on top.
+    areas: Vec<(WidgetType, Rect)>,
+}
+
+impl HitMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.areas.clear();
+    }
+
+    /// Records that `widget` was drawn to `area`.
+    pub fn insert(&mut self, widget: &WidgetType, area: Rect) {
+        self.areas.push((widget.clone(), area));
+    }
+
+    /// Draws `widget` to `area` and records the area.
+    pub fn draw(
+        &mut self,
+        widget: &WidgetType,
+        area: Rect,
+        buf: &mut Buffer,
+        ret: &mut Option<u16>,
+    ) {
+        widget.borrow().draw(area, buf, ret);
+        self.insert(widget, area);
+    }
+
+    /// The area `widget` was drawn to, if it was drawn.
+    pub fn area_of(&self, widget: &WidgetType) -> Option<Rect> {
+        self.areas
+            .iter()
+            .rev()
+            .find(|(other, _)| Rc::ptr_eq(other, widget))
+            .map(|(_, area)| *area)
+    }
+
+    /// The topmost widget at the given cell.
+    pub fn hit(&self, column: u16, row: u16) -> Option<(&WidgetType, Rect)> {
+        let position = Position::new(column, row);
+        self.areas
+            .iter()
+            .rev()
+            .find(|(_, area)| area.contains(position))
+            .map(|(widget, area)| (widget, *area))
+    }
+}
+
+/// Delivers mouse events to the widget under the cursor.
+///
+/// A left click focuses the widget under the cursor. Once a button is pressed on a widget, the
+/// following drags and the release go to that widget, wherever they happen.
+#[derive(Debug, Default)]
+pub struct MouseRouter {
+    captured: Option<(WidgetType, Rect)>,
+}
+
+impl MouseRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle_mouse_event(
+        &mut self,
+        root: &Component,
+        hits: &HitMap,
+        event: MouseEvent,
+    ) -> Option<WidgetEvent> {
+        match event.kind {
+            MouseEventKind::Down(button) => {
+                let (widget, area) = hits.hit(event.column, event.row)?;
+                let widget = widget.clone();
+                if button == MouseButton::Left && widget.borrow().focusable() {
+                    focus::focus(root, &widget);
+                }
+                self.captured = Some((widget.clone(), area));
+                widget.borrow_mut().handle_mouse_event(event, area)
+            }
+            MouseEventKind::Drag(_) => {
+                let (widget, area) = self.captured.as_ref()?;
+                widget.borrow_mut().handle_mouse_event(event, *area)
+            }
+            MouseEventKind::Up(_) => {
+                let (widget, area) = self.captured.take()?;
+                widget.borrow_mut().handle_mouse_event(event, area)
+            }
+            _ => {
+                let (widget, area) = hits.hit(event.column, event.row)?;
+                widget.borrow_mut().handle_mouse_event(event, area)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::{AppEventSender, Event, test_utils::dummy_event_sender};
+    use crate::widgets::{Button, InputWidget, Widget};
+    use crossterm::event::KeyModifiers;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[tokio::test]
+    async fn click_focuses_and_presses() {
+        let (sender, mut receiver) = dummy_event_sender::<&str>();
+        let sender: AppEventSender<&str> = sender.into();
+        let button: WidgetType = Button::new("ok", 'o').on_press(&sender, "ok").boxed();
+        let input: WidgetType = InputWidget::new("name").boxed();
+        input.borrow_mut().focus();
+        let root = Component::ListView(Rc::from([
+            Component::Widget(input.clone()),
+            Component::Widget(button.clone()),
+        ]));
+        let mut hits = HitMap::new();
+        hits.insert(&input, Rect::new(0, 0, 20, 3));
+        hits.insert(&button, Rect::new(0, 3, 20, 3));
+
+        let mut router = MouseRouter::new();
+        let down = MouseEventKind::Down(MouseButton::Left);
+        assert_eq!(
+            router.handle_mouse_event(&root, &hits, mouse(down, 4, 4)),
+            Some(WidgetEvent::Button)
+        );
+        assert!(!input.borrow().is_focused());
+        assert!(matches!(
+            receiver.next().await.unwrap(),
+            Event::Message("ok")
+        ));
+
+        // the release belongs to the button even outside of it
+        let up = MouseEventKind::Up(MouseButton::Left);
+        router.handle_mouse_event(&root, &hits, mouse(up, 30, 30));
+        assert!(button.borrow().is_focused());
+        assert_eq!(hits.area_of(&input), Some(Rect::new(0, 0, 20, 3)));
+        assert_eq!(
+            router.handle_mouse_event(&root, &hits, mouse(down, 30, 30)),
+            None
+        );
+    }
+}
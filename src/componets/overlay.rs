@@ -0,0 +1,188 @@
+use crate::componets::component::{Component, WidgetType};
+use crate::componets::mouse::HitMap;
+use ratatui::layout::{Constraint, Rect};
+use std::rc::Rc;
+
+/// Where an [`Overlay`] is placed on the screen.
+#[derive(Debug, Default, Clone)]
+pub enum Anchor {
+    #[default]
+    Center,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    /// Below the widget, or above it if there is no room below. Falls back to the center if the
+    /// widget was not drawn.
+    Widget(WidgetType),
+}
+
+/// Placement and stacking of a [`Floating`](Component::Floating) component.
+///
+/// Overlays are drawn after the rest of the tree, ordered by `z`, and are placed relative to
+/// the whole area the tree is rendered into, not to their parent.
+#[derive(Debug, Clone)]
+pub struct Overlay {
+    pub anchor: Anchor,
+    pub width: Constraint,
+    pub height: Constraint,
+    /// Overlays with a higher z are drawn on top, equal ones in tree order.
+    pub z: i32,
+    /// Dims everything drawn before the overlay.
+    pub backdrop: bool,
+    /// Keeps mouse clicks and the [focus](crate::componets::focus::FocusManager) inside the
+    /// overlay while it is open.
+    pub capture: bool,
+}
+
+impl Default for Overlay {
+    fn default() -> Self {
+        Self {
+            anchor: Anchor::default(),
+            width: Constraint::Percentage(50),
+            height: Constraint::Percentage(50),
+            z: 0,
+            backdrop: false,
+            capture: false,
+        }
+    }
+}
+
+impl Overlay {
+    pub fn new(anchor: Anchor, width: Constraint, height: Constraint) -> Self {
+        Self {
+            anchor,
+            width,
+            height,
+            ..Self::default()
+        }
+    }
+
+    /// A centered overlay that dims the screen and captures the input, like a dialog.
+    pub fn modal(width: Constraint, height: Constraint) -> Self {
+        Self::new(Anchor::Center, width, height)
+            .backdrop(true)
+            .capture(true)
+    }
+
+    pub fn z(mut self, z: i32) -> Self {
+        self.z = z;
+        self
+    }
+
+    pub fn backdrop(mut self, backdrop: bool) -> Self {
+        self.backdrop = backdrop;
+        self
+    }
+
+    pub fn capture(mut self, capture: bool) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// The area of the overlay on `screen`, `hits` locates [`Anchor::Widget`].
+    pub fn area(&self, screen: Rect, hits: &HitMap) -> Rect {
+        let width = resolve(self.width, screen.width);
+        let height = resolve(self.height, screen.height);
+        let centered = (
+            screen.x + (screen.width - width) / 2,
+            screen.y + (screen.height - height) / 2,
+        );
+        let (x, y) = match &self.anchor {
+            Anchor::Center => centered,
+            Anchor::TopLeft => (screen.left(), screen.top()),
+            Anchor::TopRight => (screen.right() - width, screen.top()),
+            Anchor::BottomLeft => (screen.left(), screen.bottom() - height),
+            Anchor::BottomRight => (screen.right() - width, screen.bottom() - height),
+            Anchor::Widget(widget) => match hits.area_of(widget) {
+                Some(target) if target.bottom().saturating_add(height) <= screen.bottom() => {
+                    (target.left(), target.bottom())
+                }
+                Some(target) => (target.left(), target.top().saturating_sub(height)),
+                None => centered,
+            },
+        };
+        Rect::new(x, y, width, height).intersection(screen)
+    }
+}
+
+/// The size `constraint` gives to an overlay on a screen of `size` cells.
+fn resolve(constraint: Constraint, size: u16) -> u16 {
+    let cells = match constraint {
+        Constraint::Length(cells) | Constraint::Min(cells) => cells,
+        Constraint::Max(cells) => cells.min(size),
+        Constraint::Percentage(percent) => (size as u32 * percent as u32 / 100) as u16,
+        Constraint::Ratio(numerator, denominator) => {
+            (size as u64 * numerator as u64 / denominator.max(1) as u64) as u16
+        }
+        Constraint::Fill(_) => size,
+    };
+    cells.min(size)
+}
+
+impl Component {
+    /// Shows the component as an overlay on top of the tree.
+    pub fn floating(self, overlay: Overlay) -> Self {
+        Component::Floating(overlay, Rc::new(self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widgets::{Button, Widget};
+    use ratatui::buffer::Buffer;
+
+    #[test]
+    fn anchors() {
+        let screen = Rect::new(0, 0, 80, 24);
+        let mut hits = HitMap::new();
+        let size = (Constraint::Length(20), Constraint::Length(5));
+        let at = |anchor, hits: &HitMap| Overlay::new(anchor, size.0, size.1).area(screen, hits);
+        assert_eq!(at(Anchor::Center, &hits), Rect::new(30, 9, 20, 5));
+        assert_eq!(at(Anchor::BottomRight, &hits), Rect::new(60, 19, 20, 5));
+
+        let button: WidgetType = Button::new("ok", 'o').boxed();
+        assert_eq!(
+            at(Anchor::Widget(button.clone()), &hits),
+            Rect::new(30, 9, 20, 5)
+        );
+        hits.insert(&button, Rect::new(4, 2, 10, 3));
+        assert_eq!(
+            at(Anchor::Widget(button.clone()), &hits),
+            Rect::new(4, 5, 20, 5)
+        );
+        hits.insert(&button, Rect::new(4, 20, 10, 3));
+        assert_eq!(
+            at(Anchor::Widget(button.clone()), &hits),
+            Rect::new(4, 15, 20, 5)
+        );
+
+        // a capturing overlay hides the widgets below from the mouse
+        let popup: WidgetType = Button::new("popup", 'p').boxed();
+        let root = Component::ListView(Rc::from([
+            Component::Widget(button.clone()),
+            Component::Widget(popup.clone()).floating(Overlay::modal(size.0, size.1)),
+        ]));
+        let mut buf = Buffer::empty(screen);
+        root.render(screen, &mut buf, &mut hits);
+        assert_eq!(hits.area_of(&button), None);
+        assert_eq!(hits.area_of(&popup), Some(Rect::new(30, 9, 20, 5)));
+
+        // a widget anchored overlay above it still finds the hidden widget
+        let menu: WidgetType = Button::new("menu", 'm').boxed();
+        let root = Component::ListView(Rc::from([
+            Component::Widget(button.clone()).sized(Constraint::Length(3)),
+            Component::Widget(popup.clone()).floating(Overlay::modal(size.0, size.1)),
+            Component::Widget(menu.clone())
+                .floating(Overlay::new(Anchor::Widget(button.clone()), size.0, size.1).z(1)),
+        ]));
+        root.render(screen, &mut buf, &mut hits);
+        assert_eq!(hits.area_of(&button), None);
+        assert_eq!(hits.area_of(&menu), Some(Rect::new(0, 3, 20, 5)));
+
+        // a target at the end of the coordinates does not overflow
+        hits.insert(&button, Rect::new(0, u16::MAX - 1, 10, 1));
+        assert!(at(Anchor::Widget(button.clone()), &hits).is_empty());
+    }
+}
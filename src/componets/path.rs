@@ -0,0 +1,221 @@
+use crate::componets::component::{Component, WidgetType};
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// Address of a component in a [`Component`] tree.
+///
+/// Every [`ListView`](Component::ListView), [`RowView`](Component::RowView) and
+/// [`Flex`](Component::Flex) node on the way takes one index, every [`GridView`](Component::GridView) takes two, the row and the column. A
+/// [`Floating`](Component::Floating) component is addressed like any other child of its parent,
+/// its content and the content of [`Sized`](Component::Sized) wrappers take no index. The empty path is the root itself.
+///
+/// Paths are written as the indices separated by `/`, e.g. `1/0/2`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WidgetPath(Vec<usize>);
+
+impl WidgetPath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends one index.
+    pub fn push(&mut self, index: usize) {
+        self.0.push(index);
+    }
+
+    pub fn as_slice(&self) -> &[usize] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<&[usize]> for WidgetPath {
+    fn from(indices: &[usize]) -> Self {
+        Self(indices.to_vec())
+    }
+}
+
+impl<const N: usize> From<[usize; N]> for WidgetPath {
+    fn from(indices: [usize; N]) -> Self {
+        Self(indices.to_vec())
+    }
+}
+
+impl FromStr for WidgetPath {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split('/')
+            .filter(|index| !index.is_empty())
+            .map(|index| {
+                index
+                    .trim()
+                    .parse()
+                    .wrap_err_with(|| format!("invalid index {index:?} in widget path {s:?}"))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for WidgetPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, index) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            write!(f, "{index}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A widget addressed either by its position or by its [`Widget::id`](crate::widgets::Widget::id).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WidgetRef {
+    Path(WidgetPath),
+    Id(String),
+}
+
+impl WidgetRef {
+    /// Looks the widget up in `root`.
+    pub fn resolve(&self, root: &Component) -> Option<WidgetType> {
+        match self {
+            Self::Path(path) => root.get_widget(path.as_slice()),
+            Self::Id(id) => root.find_by_id(id),
+        }
+    }
+}
+
+impl From<WidgetPath> for WidgetRef {
+    fn from(path: WidgetPath) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&str> for WidgetRef {
+    fn from(id: &str) -> Self {
+        Self::Id(id.to_string())
+    }
+}
+
+impl From<String> for WidgetRef {
+    fn from(id: String) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl Component {
+    /// The component at `path`.
+    pub fn get(&self, path: &WidgetPath) -> Option<&Component> {
+        let mut current = self;
+        let mut indices = path.as_slice().iter();
+        while let Some(index) = indices.next() {
+            current = match current.content() {
+                Component::ListView(children)
+                | Component::RowView(children)
+                | Component::Flex(_, children) => children.get(*index)?,
+                Component::GridView(rows) => rows.get(*index)?.get(*indices.next()?)?,
+                Component::Widget(_) | Component::Floating(..) | Component::Sized(..) => {
+                    return None;
+                }
+            };
+        }
+        Some(current)
+    }
+
+    /// The first widget with the given [`Widget::id`](crate::widgets::Widget::id).
+    pub fn find_by_id(&self, id: &str) -> Option<WidgetType> {
+        self.iter()
+            .find(|widget| widget.borrow().id() == Some(id))
+            .cloned()
+    }
+
+    /// The path of `widget` in this tree.
+    pub fn path_of(&self, widget: &WidgetType) -> Option<WidgetPath> {
+        let mut path = WidgetPath::new();
+        self.find_path(&mut path, &|other| Rc::ptr_eq(other, widget))
+            .then_some(path)
+    }
+
+    /// The path of the first widget with the given id.
+    pub fn path_of_id(&self, id: &str) -> Option<WidgetPath> {
+        let mut path = WidgetPath::new();
+        self.find_path(&mut path, &|widget| widget.borrow().id() == Some(id))
+            .then_some(path)
+    }
+
+    /// Extends `path` to the first widget matching `predicate`, `path` is left as it was if
+    /// there is none.
+    fn find_path(&self, path: &mut WidgetPath, predicate: &dyn Fn(&WidgetType) -> bool) -> bool {
+        match self {
+            Component::Widget(widget) => predicate(widget),
+            Component::Sized(_, child) | Component::Floating(_, child) => {
+                child.find_path(path, predicate)
+            }
+            Component::ListView(children)
+            | Component::RowView(children)
+            | Component::Flex(_, children) => children.iter().enumerate().any(|(i, child)| {
+                path.push(i);
+                let found = child.find_path(path, predicate);
+                if !found {
+                    path.0.pop();
+                }
+                found
+            }),
+            Component::GridView(rows) => rows.iter().enumerate().any(|(row, cells)| {
+                cells.iter().enumerate().any(|(column, cell)| {
+                    path.0.extend([row, column]);
+                    let found = cell.find_path(path, predicate);
+                    if !found {
+                        path.0.truncate(path.0.len() - 2);
+                    }
+                    found
+                })
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::componets::overlay::Overlay;
+    use crate::widgets::{Button, Widget};
+
+    #[test]
+    fn paths_and_ids() {
+        let ok = Button::new("ok", 'o').with_id("ok").boxed() as WidgetType;
+        let root = Component::ListView(Rc::from([
+            Component::from(Button::new("a", 'a')),
+            Component::GridView(Rc::from([
+                Rc::from([Component::from(Button::new("b", 'b'))]),
+                Rc::from([
+                    Component::from(Button::new("c", 'c')),
+                    Component::Widget(ok.clone()).floating(Overlay::default()),
+                ]),
+            ])),
+        ]));
+        let path: WidgetPath = "1/1/1".parse().unwrap();
+        assert_eq!(path, WidgetPath::from([1, 1, 1]));
+        assert_eq!(path.to_string(), "1/1/1");
+        assert_eq!(root.path_of_id("ok"), Some(path.clone()));
+        assert_eq!(root.path_of(&ok), Some(path.clone()));
+        assert!(Rc::ptr_eq(&root.find_by_id("ok").unwrap(), &ok));
+        assert!(Rc::ptr_eq(
+            &WidgetRef::Path(path).resolve(&root).unwrap(),
+            &ok
+        ));
+        assert!(WidgetRef::from("missing").resolve(&root).is_none());
+        assert!(root.get(&WidgetPath::from([1, 5, 0])).is_none());
+        // a grid takes the row and the column
+        assert!(root.get(&WidgetPath::from([1, 0])).is_none());
+        assert!("1/x".parse::<WidgetPath>().is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::componets::component::{Component, WidgetType};
+use crate::componets::flex;
+use crate::componets::mouse::HitMap;
+use crate::componets::overlay::Overlay;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Direction, Layout, Position, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Clear, Widget as UiWidget};
+
+impl Component {
+    /// Draws the tree into `area` and returns the absolute position of the cursor, if a widget
+    /// asked for one.
+    ///
+    /// [`ListView`](Component::ListView) and [`RowView`](Component::RowView) split the area
+    /// along their direction by the [constraints](Component::Sized) of their children, a
+    /// [`GridView`](Component::GridView) splits it into rows of equal height and every row like
+    /// a `RowView`. [`Flex`](Component::Flex) nodes are placed by their
+    /// [`Style`](crate::componets::style::Style). [`Floating`](Component::Floating) children
+    /// take no space, they are drawn after the rest of the tree as described by their
+    /// [`Overlay`]. `hits` is refilled with the area of every widget that can be clicked.
+    pub fn render(&self, area: Rect, buf: &mut Buffer, hits: &mut HitMap) -> Option<Position> {
+        hits.clear();
+        let mut pass = Pass {
+            screen: area,
+            buf,
+            hits,
+            drawn: HitMap::new(),
+            cursor: None,
+            overlays: Vec::new(),
+        };
+        self.render_into(area, &mut pass);
+        pass.draw_overlays();
+        pass.cursor
+    }
+
+    /// Renders the tree into the whole terminal frame and places the cursor.
+    pub fn render_frame(&self, frame: &mut ratatui::Frame, hits: &mut HitMap) {
+        let area = frame.area();
+        if let Some(cursor) = self.render(area, frame.buffer_mut(), hits) {
+            frame.set_cursor_position(cursor);
+        }
+    }
+
+    /// The constraint of the component in its parent.
+    pub fn constraint(&self) -> Constraint {
+        match self {
+            Component::Sized(constraint, _) => *constraint,
+            Component::Flex(style, _) => style.constraint(),
+            _ => Constraint::Fill(1),
+        }
+    }
+
+    fn render_into<'a>(&'a self, area: Rect, pass: &mut Pass<'a>) {
+        match self {
+            Component::Widget(widget) => pass.draw(widget, area),
+            Component::Floating(overlay, child) => pass.overlays.push((overlay, child)),
+            Component::Sized(_, child) => child.render_into(area, pass),
+            Component::ListView(children) => render_list(children, Direction::Vertical, area, pass),
+            Component::RowView(children) => {
+                render_list(children, Direction::Horizontal, area, pass)
+            }
+            Component::Flex(style, children) => {
+                let (floating, flow) = split_floating(children);
+                let areas = flex::layout(style, &flow, area);
+                for (child, area) in flow.into_iter().zip(areas) {
+                    child.render_into(area, pass);
+                }
+                for child in floating {
+                    child.render_into(area, pass);
+                }
+            }
+            Component::GridView(rows) => {
+                let areas = Layout::vertical(rows.iter().map(|_| Constraint::Fill(1))).split(area);
+                for (row, area) in rows.iter().zip(areas.iter()) {
+                    render_list(row, Direction::Horizontal, *area, pass);
+                }
+            }
+        }
+    }
+}
+
+/// State of one [`Component::render`] call.
+struct Pass<'a> {
+    /// The whole area of the tree, overlays are placed in it.
+    screen: Rect,
+    buf: &'a mut Buffer,
+    hits: &'a mut HitMap,
+    /// Every widget drawn so far, also those a capturing overlay hid from `hits`, it places
+    /// [widget anchored](crate::componets::overlay::Anchor::Widget) overlays.
+    drawn: HitMap,
+    cursor: Option<Position>,
+    /// Overlays found while drawing, they are drawn after the rest of the tree.
+    overlays: Vec<(&'a Overlay, &'a Component)>,
+}
+
+impl<'a> Pass<'a> {
+    fn draw(&mut self, widget: &WidgetType, area: Rect) {
+        let mut offset = None;
+        self.hits.draw(widget, area, self.buf, &mut offset);
+        self.drawn.insert(widget, area);
+        if let Some(offset) = offset {
+            self.cursor = Some(cursor_position(area, offset));
+        }
+    }
+
+    /// Draws the pending overlays from the lowest to the highest z, an overlay nested in
+    /// another one is drawn right after its parent.
+    fn draw_overlays(&mut self) {
+        let mut overlays = std::mem::take(&mut self.overlays);
+        overlays.sort_by_key(|(overlay, _)| overlay.z);
+        for (overlay, child) in overlays {
+            let area = overlay.area(self.screen, &self.drawn);
+            if overlay.backdrop {
+                self.buf
+                    .set_style(self.screen, Style::new().add_modifier(Modifier::DIM));
+            }
+            if overlay.capture {
+                // nothing below a capturing overlay can be clicked or show its cursor
+                self.hits.clear();
+                self.cursor = None;
+            }
+            Clear.render(area, self.buf);
+            child.render_into(area, self);
+            self.draw_overlays();
+        }
+    }
+}
+
+fn render_list<'a>(
+    children: &'a [Component],
+    direction: Direction,
+    area: Rect,
+    pass: &mut Pass<'a>,
+) {
+    let (floating, flow) = split_floating(children);
+    let areas = Layout::new(direction, flow.iter().map(|child| child.constraint())).split(area);
+    for (child, area) in flow.into_iter().zip(areas.iter()) {
+        child.render_into(*area, pass);
+    }
+    for child in floating {
+        child.render_into(area, pass);
+    }
+}
+
+/// Separates the floating children, which take no space, from the others.
+fn split_floating(children: &[Component]) -> (Vec<&Component>, Vec<&Component>) {
+    children
+        .iter()
+        .partition(|child| matches!(child.inner(), Component::Floating(..)))
+}
+
+/// Turns the cursor column returned by [`Widget::draw`](crate::widgets::Widget::draw) into a
+/// screen position.
+///
+/// The widgets draw their text on the first line inside their border, so the cursor is placed
+/// on the second row of `area`.
+fn cursor_position(area: Rect, offset: u16) -> Position {
+    let row = if area.height > 1 { 1 } else { 0 };
+    Position::new(
+        area.x + offset.min(area.width.saturating_sub(1)),
+        area.y + row,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widgets::{Button, InputWidget, Widget};
+    use std::rc::Rc;
+
+    #[test]
+    fn lists_rows_and_cursor() {
+        let input: WidgetType = InputWidget::new("name").boxed();
+        input.borrow_mut().focus();
+        input.borrow_mut().handle_paste("ab");
+        let ok: WidgetType = Button::new("ok", 'o').boxed();
+        let cancel: WidgetType = Button::new("cancel", 'c').boxed();
+        let root = Component::ListView(Rc::from([
+            Component::Widget(input.clone()).sized(Constraint::Length(3)),
+            Component::RowView(Rc::from([
+                Component::Widget(ok.clone()).sized(Constraint::Percentage(25)),
+                Component::Widget(cancel.clone()),
+            ])),
+        ]));
+        let area = Rect::new(2, 1, 20, 10);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 20));
+        let mut hits = HitMap::new();
+
+        let cursor = root.render(area, &mut buf, &mut hits);
+        assert_eq!(cursor, Some(Position::new(5, 2)));
+        assert_eq!(hits.area_of(&input), Some(Rect::new(2, 1, 20, 3)));
+        assert_eq!(hits.area_of(&ok), Some(Rect::new(2, 4, 5, 7)));
+        assert_eq!(hits.area_of(&cancel), Some(Rect::new(7, 4, 15, 7)));
+    }
+}
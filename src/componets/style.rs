@@ -0,0 +1,162 @@
+use ratatui::layout::{Constraint, Direction};
+use ratatui::widgets::Padding;
+
+/// How the children of a [`Flex`](crate::componets::component::Component::Flex) node are placed
+/// on the cross axis.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Start,
+    End,
+    Center,
+    /// Children take the whole cross size.
+    #[default]
+    Stretch,
+}
+
+/// How the free space on the main axis is distributed between the children of a
+/// [`Flex`](crate::componets::component::Component::Flex) node.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Justify {
+    #[default]
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+/// Flexbox style of a [`Flex`](crate::componets::component::Component::Flex) node.
+///
+/// `direction`, `wrap`, `align`, `justify`, `gap` and `padding` place the children of the node,
+/// the other fields size the node in its parent. Sizes are in terminal cells.
+///
+/// Unlike in CSS, items grow by default, so a node without any sizes fills its parent like a
+/// [`Constraint::Fill`].
+///
+/// The full algorithm needs the `flex` feature. Without it every node is split like a
+/// [`ListView`](crate::componets::component::Component::ListView) or
+/// [`RowView`](crate::componets::component::Component::RowView) by the grow factors and bases of
+/// its children, wrapping and alignment are ignored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub direction: Direction,
+    /// Whether children that do not fit move to a new line.
+    pub wrap: bool,
+    pub align: Align,
+    pub justify: Justify,
+    /// Space between two children, and between two lines when wrapping.
+    pub gap: u16,
+    pub padding: Padding,
+    pub grow: f32,
+    pub shrink: f32,
+    /// Size on the main axis of the parent before growing and shrinking.
+    pub basis: Option<u16>,
+    pub min_width: Option<u16>,
+    pub min_height: Option<u16>,
+    pub max_width: Option<u16>,
+    pub max_height: Option<u16>,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            direction: Direction::Horizontal,
+            wrap: false,
+            align: Align::default(),
+            justify: Justify::default(),
+            gap: 0,
+            padding: Padding::ZERO,
+            grow: 1.0,
+            shrink: 1.0,
+            basis: None,
+            min_width: None,
+            min_height: None,
+            max_width: None,
+            max_height: None,
+        }
+    }
+}
+
+impl Style {
+    /// Children side by side.
+    pub fn row() -> Self {
+        Self::default()
+    }
+
+    /// Children stacked vertically.
+    pub fn column() -> Self {
+        Self {
+            direction: Direction::Vertical,
+            ..Self::default()
+        }
+    }
+
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    /// A fixed size on the main axis of the parent, the node neither grows nor shrinks.
+    pub fn fixed(self, size: u16) -> Self {
+        self.basis(size).grow(0.0).shrink(0.0)
+    }
+
+    pub fn basis(mut self, basis: u16) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    pub fn min_size(mut self, width: u16, height: u16) -> Self {
+        self.min_width = Some(width);
+        self.min_height = Some(height);
+        self
+    }
+
+    pub fn max_size(mut self, width: u16, height: u16) -> Self {
+        self.max_width = Some(width);
+        self.max_height = Some(height);
+        self
+    }
+
+    /// The constraint that approximates the size of the node when it is split without the
+    /// flex engine.
+    pub fn constraint(&self) -> Constraint {
+        match self.basis {
+            Some(basis) if self.grow == 0.0 => Constraint::Length(basis),
+            Some(basis) => Constraint::Min(basis),
+            None if self.grow == 0.0 => Constraint::Length(0),
+            None => Constraint::Fill(self.grow.round().max(1.0) as u16),
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use crate::componets::mouse::HitMap;
+use crate::componets::overlay::{Anchor, Overlay};
+use crate::events::{AppEvent, Event};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Clear, List, Paragraph, Widget as UiWidget, Wrap};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Action that dismisses the newest visible toast, bound to `alt+x` in the
+/// [default keymap](crate::events::keymap::Keymap::default).
+pub const DISMISS_ACTION: &str = "dismiss";
+/// Action that opens and closes the history of notifications, bound to `alt+n` by default.
+pub const HISTORY_ACTION: &str = "notifications";
+/// Five seconds at the default [`TICK_FPS`](crate::events::rate::TICK_FPS).
+const DEFAULT_TIMEOUT: u32 = 150;
+const HISTORY_LIMIT: usize = 100;
+/// Height of a toast with its border.
+const TOAST_HEIGHT: u16 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ToastLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl ToastLevel {
+    fn color(self) -> Color {
+        match self {
+            ToastLevel::Info => Color::Blue,
+            ToastLevel::Warn => Color::Yellow,
+            ToastLevel::Error => Color::Red,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ToastLevel::Info => "info",
+            ToastLevel::Warn => "warn",
+            ToastLevel::Error => "error",
+        }
+    }
+}
+
+/// A short notification, sent with [`AppEventSender::notify`](crate::events::AppEventSender::notify)
+/// and shown by [`Toasts`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Toast {
+    pub level: ToastLevel,
+    pub message: String,
+    /// Number of ticks the toast stays visible, the timeout of the [`Toasts`] if not set.
+    pub ticks: Option<u32>,
+}
+
+impl Toast {
+    pub fn new(level: ToastLevel, message: &str) -> Self {
+        Self {
+            level,
+            message: String::from(message),
+            ticks: None,
+        }
+    }
+
+    pub fn info(message: &str) -> Self {
+        Self::new(ToastLevel::Info, message)
+    }
+
+    pub fn warn(message: &str) -> Self {
+        Self::new(ToastLevel::Warn, message)
+    }
+
+    pub fn error(message: &str) -> Self {
+        Self::new(ToastLevel::Error, message)
+    }
+
+    pub fn ticks(mut self, ticks: u32) -> Self {
+        self.ticks = Some(ticks);
+        self
+    }
+}
+
+/// The visible toasts and the history of all notifications.
+///
+/// Feed it every event with [`Toasts::handle_event`]: [`AppEvent::Toast`] shows a toast,
+/// [`Event::Tick`] counts down the time it stays visible and the [`DISMISS_ACTION`] and
+/// [`HISTORY_ACTION`] actions dismiss it and open the history. Draw it with
+/// [`Toasts::render`] after the rest of the screen, toasts never take the input.
+#[derive(Debug, Clone)]
+pub struct Toasts {
+    /// Visible toasts with their remaining ticks, oldest first.
+    active: VecDeque<(Toast, u32)>,
+    /// Every notification, newest first.
+    history: VecDeque<Toast>,
+    anchor: Anchor,
+    timeout: u32,
+    history_limit: usize,
+    max_visible: usize,
+    show_history: bool,
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        Self {
+            active: VecDeque::new(),
+            history: VecDeque::new(),
+            anchor: Anchor::BottomRight,
+            timeout: DEFAULT_TIMEOUT,
+            history_limit: HISTORY_LIMIT,
+            max_visible: 5,
+            show_history: false,
+        }
+    }
+
+    /// The corner the toasts stack in, the bottom right by default.
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Number of ticks a toast stays visible, unless it sets its own.
+    pub fn timeout(mut self, ticks: u32) -> Self {
+        self.timeout = ticks;
+        self
+    }
+
+    /// Number of notifications kept in the history, 100 by default.
+    pub fn history_limit(mut self, limit: usize) -> Self {
+        self.history_limit = limit;
+        self.history.truncate(limit);
+        self
+    }
+
+    /// Number of toasts shown at once, older ones are dropped from the screen.
+    pub fn max_visible(mut self, count: usize) -> Self {
+        self.max_visible = count;
+        self
+    }
+
+    pub fn push(&mut self, toast: Toast) {
+        self.history.push_front(toast.clone());
+        self.history.truncate(self.history_limit);
+        let ticks = toast.ticks.unwrap_or(self.timeout);
+        self.active.push_back((toast, ticks));
+        while self.active.len() > self.max_visible {
+            self.active.pop_front();
+        }
+    }
+
+    /// Counts down the visible toasts and removes the expired ones.
+    pub fn tick(&mut self) {
+        for (_, ticks) in self.active.iter_mut() {
+            *ticks = ticks.saturating_sub(1);
+        }
+        self.active.retain(|(_, ticks)| *ticks > 0);
+    }
+
+    /// Removes the newest visible toast, returns it.
+    pub fn dismiss(&mut self) -> Option<Toast> {
+        self.active.pop_back().map(|(toast, _)| toast)
+    }
+
+    pub fn dismiss_all(&mut self) {
+        self.active.clear();
+    }
+
+    pub fn toggle_history(&mut self) {
+        self.show_history = !self.show_history;
+    }
+
+    pub fn is_history_shown(&self) -> bool {
+        self.show_history
+    }
+
+    /// The visible toasts, oldest first.
+    pub fn active(&self) -> impl DoubleEndedIterator<Item = &Toast> {
+        self.active.iter().map(|(toast, _)| toast)
+    }
+
+    /// Past notifications, newest first.
+    pub fn history(&self) -> impl Iterator<Item = &Toast> {
+        self.history.iter()
+    }
+
+    /// Handles the toast events, returns whether `event` was used up.
+    ///
+    /// Ticks are counted but never used up, the application may need them as well.
+    pub fn handle_event<M>(&mut self, event: &Event<M>) -> bool {
+        match event {
+            Event::Tick => {
+                self.tick();
+                false
+            }
+            Event::App(AppEvent::Toast(toast)) => {
+                self.push(toast.clone());
+                true
+            }
+            Event::App(AppEvent::Action(action)) if action == DISMISS_ACTION => {
+                // let the key through if there was nothing to dismiss
+                self.dismiss().is_some()
+            }
+            Event::App(AppEvent::Action(action)) if action == HISTORY_ACTION => {
+                self.toggle_history();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Draws the toasts stacked in their corner of `area`, newest closest to the corner, and
+    /// the history on top if it is open.
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        let hits = HitMap::new();
+        if !self.active.is_empty() {
+            let width = Constraint::Length(area.width.min(40));
+            let height = Constraint::Length(self.active.len() as u16 * TOAST_HEIGHT);
+            let stack = Overlay::new(self.anchor.clone(), width, height).area(area, &hits);
+            let rows =
+                Layout::vertical(self.active.iter().map(|_| Constraint::Length(TOAST_HEIGHT)))
+                    .split(stack);
+            let newest_first = matches!(self.anchor, Anchor::TopLeft | Anchor::TopRight);
+            let toasts: Vec<&Toast> = if newest_first {
+                self.active().rev().collect()
+            } else {
+                self.active().collect()
+            };
+            for (toast, area) in toasts.into_iter().zip(rows.iter()) {
+                Clear.render(*area, buf);
+                Paragraph::new(toast.message.as_str())
+                    .wrap(Wrap { trim: true })
+                    .block(
+                        Block::bordered()
+                            .border_type(BorderType::Rounded)
+                            .border_style(Style::new().fg(toast.level.color()))
+                            .title(toast.level.label()),
+                    )
+                    .render(*area, buf);
+            }
+        }
+
+        if self.show_history {
+            let size = Constraint::Percentage(60);
+            let area = Overlay::new(Anchor::Center, size, size).area(area, &hits);
+            Clear.render(area, buf);
+            List::new(self.history.iter().map(|toast| {
+                Line::from(format!("{:>5}  {}", toast.level.label(), toast.message))
+                    .style(Style::new().fg(toast.level.color()))
+            }))
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .title("Notifications"),
+            )
+            .render(area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::source::{ManualClock, ScriptedSource};
+    use crate::events::{AppEventSender, EventHandler, test_utils::dummy_event_sender};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[tokio::test]
+    async fn toasts_expire_and_stay_in_history() {
+        let (sender, mut receiver) = dummy_event_sender::<()>();
+        let sender: AppEventSender = sender.into();
+        sender.notify(Toast::info("saved").ticks(2));
+        sender.error("failed");
+        let mut toasts = Toasts::new().timeout(3);
+        for _ in 0..2 {
+            assert!(toasts.handle_event(&receiver.next().await.unwrap()));
+        }
+        assert_eq!(toasts.active().count(), 2);
+
+        toasts.handle_event::<()>(&Event::Tick);
+        toasts.handle_event::<()>(&Event::Tick);
+        let active: Vec<_> = toasts.active().map(|t| t.message.as_str()).collect();
+        assert_eq!(active, ["failed"]);
+        assert!(toasts.handle_event::<()>(&Event::App(AppEvent::Action(DISMISS_ACTION.into()))));
+        assert!(!toasts.handle_event::<()>(&Event::App(AppEvent::Action(DISMISS_ACTION.into()))));
+
+        // the default keymap turns alt+x and alt+n into the toast actions
+        let alt = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+        let mut events: EventHandler = EventHandler::builder()
+            .manual_clock(ManualClock::new())
+            .source(
+                ScriptedSource::new()
+                    .key_event(alt('x'))
+                    .key_event(alt('n')),
+            )
+            .build();
+        toasts.push(Toast::warn("offline"));
+        assert!(toasts.handle_event(&events.next().await.unwrap()));
+        assert_eq!(toasts.active().count(), 0);
+        assert!(toasts.handle_event(&events.next().await.unwrap()));
+        assert!(toasts.is_history_shown());
+        toasts.toggle_history();
+        events.stop();
+
+        let history: Vec<_> = toasts.history().map(|t| t.level).collect();
+        assert_eq!(
+            history,
+            [ToastLevel::Warn, ToastLevel::Error, ToastLevel::Info]
+        );
+
+        let area = Rect::new(0, 0, 60, 20);
+        let mut buf = Buffer::empty(area);
+        toasts.push(Toast::warn("disk almost full"));
+        toasts.render(area, &mut buf);
+        // stacked in the bottom right corner
+        assert_eq!(buf[(21, 18)].symbol(), "d");
+    }
+}
@@ -0,0 +1,281 @@
+use crate::app::{App, Runner};
+use crate::componets::component::Component;
+use crate::events::{AppEvent, AppEventSender, Event};
+use crate::widgets::WidgetEvent;
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::pin::Pin;
+
+type Update<Model, M> = Box<dyn FnMut(&mut Model, Msg<M>) -> Command<M>>;
+type View<Model> = Box<dyn Fn(&Model) -> Component>;
+type Task<M> = Pin<Box<dyn Future<Output = M> + Send>>;
+
+/// What the update function of a [`Program`] is called with.
+#[derive(Debug)]
+pub enum Msg<M> {
+    /// A typed message from a widget, a command or a background task.
+    App(M),
+    /// A widget reported what happened, e.g. that an input was submitted.
+    Widget(WidgetEvent),
+    /// Every other event: ticks, actions, keys nothing had focus for, resizes.
+    Event(Event<M>),
+}
+
+/// Work the runtime does after an update.
+pub enum Command<M> {
+    None,
+    Batch(Vec<Command<M>>),
+    /// Sends the message back to the update function.
+    Message(M),
+    /// Sends the event to the event loop.
+    Send(AppEvent),
+    /// Runs the future in the background and sends its result as a message.
+    Spawn(Task<M>),
+    Quit,
+}
+
+impl<M> Command<M> {
+    pub fn none() -> Self {
+        Command::None
+    }
+
+    pub fn batch(commands: impl IntoIterator<Item = Command<M>>) -> Self {
+        Command::Batch(commands.into_iter().collect())
+    }
+
+    pub fn message(message: M) -> Self {
+        Command::Message(message)
+    }
+
+    pub fn send(event: AppEvent) -> Self {
+        Command::Send(event)
+    }
+
+    pub fn spawn(task: impl Future<Output = M> + Send + 'static) -> Self {
+        Command::Spawn(Box::pin(task))
+    }
+
+    pub fn quit() -> Self {
+        Command::Quit
+    }
+}
+
+impl<M: Send + 'static> Command<M> {
+    fn run(self, sender: &AppEventSender<M>) {
+        match self {
+            Command::None => {}
+            Command::Batch(commands) => commands.into_iter().for_each(|c| c.run(sender)),
+            Command::Message(message) => sender.send_message(message),
+            Command::Send(event) => sender.send(event),
+            Command::Spawn(task) => {
+                let sender = sender.clone();
+                tokio::spawn(async move { sender.send_message(task.await) });
+            }
+            Command::Quit => sender.send(AppEvent::Quit),
+        }
+    }
+}
+
+impl<M: Debug> Debug for Command<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::None => f.write_str("None"),
+            Command::Batch(commands) => f.debug_tuple("Batch").field(commands).finish(),
+            Command::Message(message) => f.debug_tuple("Message").field(message).finish(),
+            Command::Send(event) => f.debug_tuple("Send").field(event).finish(),
+            Command::Spawn(_) => f.write_str("Spawn"),
+            Command::Quit => f.write_str("Quit"),
+        }
+    }
+}
+
+/// An application in the Elm architecture: a model, a function that updates it and a function
+/// that shows it.
+///
+/// Every event goes through `update`, the returned [`Command`] is run and the tree returned by
+/// `view` is drawn again. `view` is called for every frame, so keep the widgets in the model
+/// and only arrange them there, widgets built in `view` lose their state on every frame.
+///
+/// Commands returned by updates before the program was [started](App::init) are queued and run
+/// right after the [init command](Program::with_init).
+pub struct Program<Model, M = ()> {
+    model: Model,
+    update: Update<Model, M>,
+    view: View<Model>,
+    init: Option<Command<M>>,
+    sender: Option<AppEventSender<M>>,
+    /// Commands of updates before `init`.
+    queued: Vec<Command<M>>,
+}
+
+impl<Model, M: Send + 'static> Program<Model, M> {
+    pub fn new(
+        model: Model,
+        update: impl FnMut(&mut Model, Msg<M>) -> Command<M> + 'static,
+        view: impl Fn(&Model) -> Component + 'static,
+    ) -> Self {
+        Self {
+            model,
+            update: Box::new(update),
+            view: Box::new(view),
+            init: None,
+            sender: None,
+            queued: Vec::new(),
+        }
+    }
+
+    /// Runs `command` when the program starts, e.g. to load data in the background.
+    pub fn with_init(mut self, command: Command<M>) -> Self {
+        self.init = Some(command);
+        self
+    }
+
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    /// Runs the program with the default [`Runner`] and returns the final model.
+    pub async fn run(self) -> color_eyre::Result<Model> {
+        self.run_with(Runner::new()).await
+    }
+
+    pub async fn run_with(mut self, runner: Runner<M>) -> color_eyre::Result<Model> {
+        runner.run(&mut self).await?;
+        Ok(self.model)
+    }
+
+    fn update(&mut self, message: Msg<M>) {
+        let command = (self.update)(&mut self.model, message);
+        match self.sender.as_ref() {
+            Some(sender) => command.run(sender),
+            None => self.queued.push(command),
+        }
+    }
+}
+
+impl<Model, M: Send + 'static> App for Program<Model, M> {
+    type Message = M;
+
+    fn init(&mut self, sender: AppEventSender<M>) -> color_eyre::Result<()> {
+        if let Some(command) = self.init.take() {
+            command.run(&sender);
+        }
+        for command in self.queued.drain(..) {
+            command.run(&sender);
+        }
+        self.sender = Some(sender);
+        Ok(())
+    }
+
+    fn root(&self) -> Component {
+        (self.view)(&self.model)
+    }
+
+    fn handle_event(&mut self, event: Event<M>) -> color_eyre::Result<()> {
+        match event {
+            Event::Message(message) => self.update(Msg::App(message)),
+            event => self.update(Msg::Event(event)),
+        }
+        Ok(())
+    }
+
+    fn handle_widget_event(&mut self, event: WidgetEvent) -> color_eyre::Result<()> {
+        self.update(Msg::Widget(event));
+        Ok(())
+    }
+}
+
+impl<Model: Debug, M> Debug for Program<Model, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Program")
+            .field("model", &self.model)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::test_utils::dummy_event_sender;
+    use crate::widgets::{Button, Widget};
+    use std::rc::Rc;
+
+    #[derive(Debug)]
+    enum Counter {
+        Add(u32),
+        Loaded(u32),
+    }
+
+    #[tokio::test]
+    async fn update_runs_commands() {
+        let button = Button::new("add", 'a').boxed();
+        let view_button = button.clone();
+        let mut program = Program::new(
+            0,
+            |count: &mut u32, message| match message {
+                Msg::App(Counter::Add(n)) => {
+                    *count += n;
+                    Command::spawn(async { Counter::Loaded(10) })
+                }
+                Msg::App(Counter::Loaded(n)) => {
+                    *count += n;
+                    Command::quit()
+                }
+                Msg::Widget(WidgetEvent::Button) => Command::message(Counter::Add(1)),
+                _ => Command::none(),
+            },
+            move |_| Component::Widget(view_button.clone()),
+        )
+        .with_init(Command::message(Counter::Add(2)));
+        let (sender, mut receiver) = dummy_event_sender();
+        program.init(sender.into()).unwrap();
+
+        let event = receiver.next().await.unwrap();
+        program.handle_event(event).unwrap();
+        assert_eq!(*program.model(), 2);
+        program
+            .handle_event(receiver.next().await.unwrap())
+            .unwrap();
+        assert_eq!(*program.model(), 12);
+        assert!(matches!(
+            receiver.next().await.unwrap(),
+            Event::App(AppEvent::Quit)
+        ));
+
+        program.handle_widget_event(WidgetEvent::Button).unwrap();
+        program
+            .handle_event(receiver.next().await.unwrap())
+            .unwrap();
+        assert_eq!(*program.model(), 13);
+        assert_eq!(program.root().iter().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn commands_before_init_are_queued() {
+        let mut program = Program::new(
+            0,
+            |count: &mut u32, message| match message {
+                Msg::App(Counter::Add(n)) => {
+                    *count += n;
+                    Command::none()
+                }
+                Msg::Widget(_) => Command::message(Counter::Add(1)),
+                _ => Command::none(),
+            },
+            |_| Component::ListView(Rc::from([])),
+        )
+        .with_init(Command::message(Counter::Add(2)));
+        program.handle_widget_event(WidgetEvent::Button).unwrap();
+        assert_eq!(*program.model(), 0);
+
+        let (sender, mut receiver) = dummy_event_sender();
+        program.init(sender.into()).unwrap();
+        // the init command runs first
+        for total in [2, 3] {
+            program
+                .handle_event(receiver.next().await.unwrap())
+                .unwrap();
+            assert_eq!(*program.model(), total);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::marker;
 use std::sync::Arc;
@@ -5,12 +6,14 @@ use std::sync::Arc;
 type Int = u64;
 type Float = f64;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum AnyType {
     AnyString(String),
     AnyInt(Int),
     AnyFloat(Float),
     AnyChar(char),
+    /// Cannot be serialized, events carrying it are not recorded.
+    #[serde(skip)]
     AnyCustom(Arc<dyn Any + marker::Send + marker::Sync>),
 }
 
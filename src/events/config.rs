@@ -0,0 +1,172 @@
+use crate::events::keymap::{Keymap, parse_sequence};
+use color_eyre::eyre::{WrapErr, eyre};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml::Spanned;
+
+/// Name of the user keymap file inside the application config directory.
+const KEYMAP_FILE: &str = "keymap.toml";
+
+/// Layout of a keymap file.
+///
+/// ```toml
+/// timeout_ms = 500
+///
+/// [modes.global]
+/// "ctrl+q" = "quit"
+///
+/// [modes.normal]
+/// "g g" = "top"
+/// "ctrl+x ctrl+s" = "save"
+/// "ctrl+c" = ""  # an empty action removes the binding
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeymapFile {
+    timeout_ms: Option<u64>,
+    #[serde(default)]
+    modes: BTreeMap<String, BTreeMap<Spanned<String>, Spanned<String>>>,
+}
+
+impl Keymap {
+    /// Applies the bindings of the TOML document `source` on top of this keymap.
+    ///
+    /// `origin` names the document in error messages, usually the path it was read from.
+    pub fn merge_toml(&mut self, source: &str, origin: &str) -> color_eyre::Result<()> {
+        let file: KeymapFile = toml::from_str(source).map_err(|err| {
+            let location = err
+                .span()
+                .map(|span| location(source, span))
+                .unwrap_or_default();
+            eyre!("{origin}{location}: {}", err.message().trim_end())
+        })?;
+        if let Some(timeout) = file.timeout_ms {
+            self.set_timeout(Duration::from_millis(timeout));
+        }
+        for (mode, bindings) in file.modes {
+            for (keys, action) in bindings {
+                let sequence = parse_sequence(keys.get_ref()).wrap_err_with(|| {
+                    format!(
+                        "{origin}{}: invalid key binding",
+                        location(source, keys.span())
+                    )
+                })?;
+                if action.get_ref().is_empty() {
+                    self.unbind_sequence(&mode, &sequence);
+                } else {
+                    self.bind_sequence(&mode, sequence, action.get_ref());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the bindings of the file at `path` on top of this keymap.
+    pub fn merge_file(&mut self, path: impl AsRef<Path>) -> color_eyre::Result<()> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read keymap {}", path.display()))?;
+        self.merge_toml(&source, &path.display().to_string())
+    }
+
+    /// Builds the keymap of the application `app_name`.
+    ///
+    /// The layers are applied in order, later ones win:
+    /// 1. the [default bindings](Keymap::default),
+    /// 2. `app_bindings`, the TOML document shipped with the application,
+    /// 3. the users `keymap.toml` in the config directory of the application, if it exists.
+    pub fn layered(app_name: &str, app_bindings: Option<&str>) -> color_eyre::Result<Self> {
+        let mut keymap = Self::default();
+        if let Some(app_bindings) = app_bindings {
+            keymap.merge_toml(app_bindings, app_name)?;
+        }
+        if let Some(path) = user_keymap_path(app_name).filter(|path| path.exists()) {
+            keymap.merge_file(path)?;
+        }
+        Ok(keymap)
+    }
+}
+
+/// Location of the user keymap of the application `app_name`.
+pub fn user_keymap_path(app_name: &str) -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join(app_name).join(KEYMAP_FILE))
+}
+
+/// Turns a byte range in `source` into a `:line:column` suffix.
+fn location(source: &str, span: Range<usize>) -> String {
+    let before = &source[..span.start.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+    format!(":{line}:{column}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::keymap::{DEFAULT_MODE, GLOBAL_MODE, KeyChord, KeyResolver, Resolved};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    const APP: &str = r#"
+[modes.normal]
+"g g" = "top"
+"q" = "quit"
+"#;
+
+    const USER: &str = r#"
+timeout_ms = 250
+
+[modes.normal]
+"q" = ""
+"g g" = "bottom"
+
+[modes.global]
+"ctrl+c" = ""
+"#;
+
+    fn resolve(keymap: &Keymap, keys: &str) -> Vec<Resolved> {
+        let mut resolver = KeyResolver::new();
+        parse_sequence(keys)
+            .unwrap()
+            .into_iter()
+            .flat_map(|KeyChord { code, modifiers }| {
+                resolver.resolve(keymap, KeyEvent::new(code, modifiers))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn layers_override() {
+        let mut keymap = Keymap::default();
+        keymap.merge_toml(APP, "app").unwrap();
+        assert_eq!(resolve(&keymap, "q"), vec![Resolved::Action("quit".into())]);
+        keymap.merge_toml(USER, "user").unwrap();
+        assert_eq!(keymap.get_timeout(), Duration::from_millis(250));
+        assert_eq!(keymap.mode(), DEFAULT_MODE);
+        assert_eq!(
+            resolve(&keymap, "g g"),
+            vec![Resolved::Action("bottom".into())]
+        );
+        let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(resolve(&keymap, "q"), vec![Resolved::Key(q)]);
+        keymap.set_mode(GLOBAL_MODE);
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(resolve(&keymap, "ctrl+c"), vec![Resolved::Key(ctrl_c)]);
+    }
+
+    #[test]
+    fn errors_point_at_source() {
+        let mut keymap = Keymap::default();
+        let err = keymap
+            .merge_toml("[modes.normal]\n\"ctrl+nope\" = \"x\"\n", "keymap.toml")
+            .unwrap_err();
+        assert!(err.to_string().starts_with("keymap.toml:2:1:"), "{err}");
+        let err = keymap
+            .merge_toml("[modes.normal]\n\"a\" = \n", "keymap.toml")
+            .unwrap_err();
+        assert!(err.to_string().starts_with("keymap.toml:2:"), "{err}");
+    }
+}
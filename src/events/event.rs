@@ -1,23 +1,43 @@
+use crate::componets::path::WidgetRef;
+use crate::componets::toast::Toast;
 use crate::events::any;
-use color_eyre::eyre::OptionExt;
-use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
-use futures::{FutureExt, StreamExt};
+use crate::events::keymap::{self, KeyResolver, Keymap, Resolved, SharedKeymap};
+use crate::events::rate::Rates;
+use crate::events::record::{self, Recorder, Recording, ReplaySpeed};
+use crate::events::request::{self, REQUEST_TIMEOUT, Request};
+use crate::events::source::{CrosstermSource, EventSource, ManualClock};
+use crate::events::task::{TaskEvent, TaskManager, Tasks};
+use crate::events::timer::{self, TimerHandle};
+use color_eyre::eyre::eyre;
+use crossterm::event::{Event as CrosstermEvent, KeyEvent};
+use futures::FutureExt;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
+use tokio::time::Instant;
 
-/// The frequency at which tick events are emitted.
-const TICK_FPS: f64 = 30.0; // 1.0;
+/// Consecutive read errors after which the event task stops reading from its source.
+const MAX_READ_ERRORS: u32 = 3;
 
 /// Representation of all possible events.
-#[derive(Clone, Debug)]
-pub enum Event {
+///
+/// `M` is the message type of the application, see [`Event::Message`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Event<M = ()> {
     /// An event that is emitted on a regular schedule.
     ///
     /// Use this event to run any code which has to run outside of being a direct response to a user
     /// event. e.g. polling exernal systems, updating animations, or rendering the UI based on a
     /// fixed frame rate.
     Tick,
+    /// An event that is emitted at the render rate, see [`EventHandlerBuilder::render_rate`].
+    ///
+    /// Use this event to draw the UI independently of the tick rate.
+    Render,
     /// Crossterm events.
     ///
     /// These events are emitted by the terminal.
@@ -26,15 +46,20 @@ pub enum Event {
     ///
     /// Use this event to emit custom events that are specific to your application.
     App(AppEvent),
+    /// Typed application messages.
+    ///
+    /// Prefer this over [`AppEvent::CustomEvent`], the compiler checks that every message is
+    /// handled.
+    Message(M),
 }
 
-impl From<AppEvent> for Event {
+impl<M> From<AppEvent> for Event<M> {
     fn from(app_event: AppEvent) -> Self {
         Self::App(app_event)
     }
 }
 
-impl From<CrosstermEvent> for Event {
+impl<M> From<CrosstermEvent> for Event<M> {
     fn from(event: CrosstermEvent) -> Self {
         Self::Crossterm(event)
     }
@@ -43,53 +68,255 @@ impl From<CrosstermEvent> for Event {
 /// Application events.
 ///
 /// You can extend this enum with your own custom events.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum AppEvent {
     /// Quit the application.
     Quit,
-    // TODO: rethink how to address items
-    FocusItem(usize),
+    /// Focus a widget, by path or by id.
+    FocusItem(WidgetRef),
+    /// Show the screen registered under this name on top of the current one, see
+    /// [`ScreenStack`](crate::componets::frame::ScreenStack).
+    PushScreen(String),
+    /// Close the current screen and go back to the one below.
+    PopScreen,
+    /// Close the current screen and show the screen registered under this name instead.
+    ReplaceScreen(String),
+    /// Show a notification, see [`Toasts`](crate::componets::toast::Toasts).
+    Toast(Toast),
+    /// A background task started, progressed or ended, see [`TaskManager`].
+    Task(TaskEvent),
     Clear(bool),
+    /// A key that is not bound in the active [`Keymap`].
     KeyEvent(KeyEvent),
+    /// A named action the [`Keymap`] resolved the pressed keys to.
+    Action(String),
+    /// A question that expects an answer, see [`AppEventSender::request`].
+    Request(Request),
     /// Custom Event has a name and a list of arguments
+    ///
+    /// Fallback for messages that do not fit into the message type of [`Event`].
     CustomEvent(String, Option<Arc<[any::AnyType]>>),
 }
 
 /// Terminal event handler.
 #[derive(Debug)]
-pub struct EventHandler {
+pub struct EventHandler<M = ()> {
     /// Event sender channel.
-    sender: mpsc::UnboundedSender<Event>,
+    sender: mpsc::UnboundedSender<Event<M>>,
     /// Event receiver channel.
-    receiver: mpsc::UnboundedReceiver<Event>,
+    receiver: mpsc::UnboundedReceiver<Event<M>>,
+    /// Events of the event task or a replay, the only ones that are recorded.
+    inputs: mpsc::UnboundedReceiver<Event<M>>,
     handle: tokio::task::JoinHandle<Result<(), color_eyre::eyre::Error>>,
+    /// Key bindings used by the event task.
+    keymap: SharedKeymap,
+    /// Tick and render rates used by the event task.
+    rates: watch::Sender<Rates>,
+    /// Records the inputs returned by [`EventHandler::next`].
+    recorder: Option<Recorder>,
+    /// Background tasks, aborted by [`EventHandler::stop`].
+    tasks: Tasks,
+}
+
+/// Builder for an [`EventHandler`], see [`EventHandler::builder`].
+pub struct EventHandlerBuilder<M = ()> {
+    keymap: Keymap,
+    rates: Rates,
+    /// Defaults to a [`CrosstermSource`].
+    source: Option<Box<dyn EventSource>>,
+    clock: Option<ManualClock>,
+    /// Replaces the source and the clock if set.
+    replay: Option<(Recording<M>, ReplaySpeed)>,
+    recorder: Option<Recorder>,
+    message: PhantomData<fn() -> M>,
+}
+
+impl<M: Send + 'static> EventHandlerBuilder<M> {
+    fn new() -> Self {
+        Self {
+            keymap: Keymap::default(),
+            rates: Rates::default(),
+            source: None,
+            clock: None,
+            replay: None,
+            recorder: None,
+            message: PhantomData,
+        }
+    }
+
+    /// Reads terminal events from `source` instead of the terminal.
+    pub fn source(mut self, source: impl EventSource) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    /// Emits ticks and render events only when `clock` asks for them, the rates are ignored.
+    pub fn manual_clock(mut self, clock: ManualClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Resolves keys with `keymap` instead of the [default keymap](Keymap::default).
+    pub fn keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Emits [`Event::Tick`] `fps` times per second, defaults to
+    /// [`TICK_FPS`](crate::events::rate::TICK_FPS).
+    pub fn tick_rate(mut self, fps: f64) -> Self {
+        self.rates.tick_fps = fps;
+        self
+    }
+
+    /// Emits [`Event::Render`] `fps` times per second, by default no render events are emitted.
+    pub fn render_rate(mut self, fps: f64) -> Self {
+        self.rates.render_fps = Some(fps);
+        self
+    }
+
+    /// Drops the tick and render rate to `idle_fps` once there was no input for `idle_after`.
+    ///
+    /// The configured rates apply again with the next input.
+    pub fn adaptive(mut self, idle_after: Duration, idle_fps: f64) -> Self {
+        self.rates = self.rates.adaptive(idle_after, idle_fps);
+        self
+    }
+
+    /// Records the inputs returned by [`EventHandler::next`] to `path`, see [`Recorder`].
+    ///
+    /// Inputs are the events of the terminal, the clock and the keymap. What the application
+    /// sends itself, e.g. messages, timers, task results and toasts, is left out, so a replay
+    /// into the same application does not produce those events twice.
+    pub fn record(mut self, path: impl AsRef<Path>) -> color_eyre::Result<Self> {
+        self.recorder = Some(Recorder::create(path)?);
+        Ok(self)
+    }
+
+    /// Plays back a recording made with [`EventHandlerBuilder::record`] instead of reading the
+    /// terminal.
+    ///
+    /// The recorded events already passed the keymap, so they are sent as they are.
+    pub fn replay(mut self, path: impl AsRef<Path>, speed: ReplaySpeed) -> color_eyre::Result<Self>
+    where
+        M: DeserializeOwned,
+    {
+        self.replay = Some((record::read_recording(path)?, speed));
+        Ok(self)
+    }
+
+    /// Constructs the [`EventHandler`] and spawns a new thread to handle events.
+    pub fn build(self) -> EventHandler<M> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let (input_sender, inputs) = mpsc::unbounded_channel();
+        let keymap = self.keymap.shared();
+        let (rates, rates_receiver) = watch::channel(self.rates);
+        let handle = match self.replay {
+            Some((events, speed)) => tokio::spawn(record::replay(input_sender, events, speed)),
+            None => {
+                let source = self
+                    .source
+                    .unwrap_or_else(|| Box::new(CrosstermSource::new()));
+                let actor = EventTask {
+                    sender: input_sender,
+                    keymap: keymap.clone(),
+                    resolver: KeyResolver::new(),
+                    rates: rates_receiver,
+                    source,
+                    clock: self.clock,
+                };
+                tokio::spawn(async { actor.run().await })
+            }
+        };
+        EventHandler {
+            sender,
+            receiver,
+            inputs,
+            handle,
+            keymap,
+            rates,
+            recorder: self.recorder,
+            tasks: Tasks::default(),
+        }
+    }
 }
 
-impl Default for EventHandler {
+impl<M: Send + 'static> Default for EventHandler<M> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl EventHandler {
+impl<M: Send + 'static> EventHandler<M> {
     /// Constructs a new instance of [`EventHandler`] and spawns a new thread to handle events.
+    ///
+    /// Uses the [default keymap](Keymap::default), which binds `ctrl+c` to quit and the keys
+    /// that dismiss and list notifications.
     pub fn new() -> Self {
-        let (sender, receiver) = mpsc::unbounded_channel();
-        let actor = EventTask::new(sender.clone());
-        let handle = tokio::spawn(async { actor.run().await });
-        Self {
-            sender,
-            receiver,
-            handle,
+        Self::builder().build()
+    }
+
+    /// Constructs a new instance of [`EventHandler`] that resolves keys with `keymap`.
+    pub fn with_keymap(keymap: Keymap) -> Self {
+        Self::builder().keymap(keymap).build()
+    }
+
+    /// Configures the keymap and the tick and render rates before the event task is started.
+    pub fn builder() -> EventHandlerBuilder<M> {
+        EventHandlerBuilder::new()
+    }
+
+    /// Stops emitting [`Event::Tick`] until [`EventHandler::resume_ticks`] is called.
+    pub fn pause_ticks(&self) {
+        self.rates.send_modify(|rates| rates.paused = true);
+    }
+
+    pub fn resume_ticks(&self) {
+        self.rates.send_modify(|rates| rates.paused = false);
+    }
+
+    pub fn set_tick_rate(&self, fps: f64) {
+        self.rates.send_modify(|rates| rates.tick_fps = fps);
+    }
+
+    /// Sets the rate of [`Event::Render`], `None` stops render events.
+    pub fn set_render_rate(&self, fps: Option<f64>) {
+        self.rates.send_modify(|rates| rates.render_fps = fps);
+    }
+
+    /// The rates currently used by the event task.
+    pub fn rates(&self) -> Rates {
+        *self.rates.borrow()
+    }
+
+    /// Switches the keymap to another mode, e.g. `insert` while a text field is edited.
+    pub fn set_mode(&self, mode: &str) {
+        if let Ok(mut keymap) = self.keymap.lock() {
+            keymap.set_mode(mode);
         }
     }
 
-    pub fn get_event_sender(&self) -> EventSender {
-        EventSender::new(self.sender.clone())
+    /// The keymap used by the event task, changes apply to the next key press.
+    pub fn keymap(&self) -> SharedKeymap {
+        self.keymap.clone()
     }
 
+    pub fn get_event_sender(&self) -> EventSender<M> {
+        EventSender {
+            sender: self.sender.clone(),
+            tasks: self.tasks.clone(),
+        }
+    }
+
+    /// Spawns background tasks whose results arrive as events.
+    pub fn tasks(&self) -> TaskManager<M> {
+        TaskManager::new(self.tasks.clone(), self.sender.clone())
+    }
+
+    /// Stops the event task and aborts all background tasks.
     pub fn stop(&self) {
         self.handle.abort();
+        self.tasks.abort_all();
     }
 
     /// Receives an event from the sender.
@@ -101,11 +328,40 @@ impl EventHandler {
     /// This function returns an error if the sender channel is disconnected. This can happen if an
     /// error occurs in the event thread. In practice, this should not happen unless there is a
     /// problem with the underlying terminal.
-    pub async fn next(&mut self) -> color_eyre::Result<Event> {
-        self.receiver
-            .recv()
-            .await
-            .ok_or_eyre("Failed to receive event")
+    ///
+    /// If the event cannot be recorded the recording stops and an error [`Toast`] is queued, the
+    /// event is returned anyway.
+    pub async fn next(&mut self) -> color_eyre::Result<Event<M>> {
+        let (event, input) = tokio::select! {
+            biased;
+            // the application handles what it sent itself before the next input, as it would
+            // have while the recording was made
+            Some(event) = self.receiver.recv() => (event, false),
+            Some(event) = self.inputs.recv() => (event, true),
+            else => return Err(eyre!("Failed to receive event")),
+        };
+        if input
+            && let Some(recorder) = self.recorder.as_mut()
+            && let Err(error) = recorder.record(&event)
+        {
+            self.recorder = None;
+            let message = format!("Recording stopped: {error}");
+            let _ = self
+                .sender
+                .send(AppEvent::Toast(Toast::error(&message)).into());
+        }
+        Ok(event)
+    }
+
+    /// Records the inputs returned by [`EventHandler::next`] to `path` from now on, see
+    /// [`EventHandlerBuilder::record`].
+    pub fn record(&mut self, path: impl AsRef<Path>) -> color_eyre::Result<()> {
+        self.recorder = Some(Recorder::create(path)?);
+        Ok(())
+    }
+
+    pub fn stop_recording(&mut self) {
+        self.recorder = None;
     }
 
     /// Queue an app event to be sent to the event receiver.
@@ -117,50 +373,210 @@ impl EventHandler {
         // reference to it
         let _ = self.sender.send(Event::App(app_event));
     }
+
+    /// Queue an application message to be sent to the event receiver.
+    pub fn send_message(&mut self, message: M) {
+        // Ignore the result as the reciever cannot be dropped while this struct still has a
+        // reference to it
+        let _ = self.sender.send(Event::Message(message));
+    }
 }
 ///Simple struct so Screens can send back events to app
-#[derive(Debug, Clone)]
-pub struct EventSender {
-    sender: mpsc::UnboundedSender<Event>,
+#[derive(Debug)]
+pub struct EventSender<M = ()> {
+    sender: mpsc::UnboundedSender<Event<M>>,
+    tasks: Tasks,
+}
+
+// derived Clone would require `M: Clone`
+impl<M> Clone for EventSender<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            tasks: self.tasks.clone(),
+        }
+    }
 }
 
-impl EventSender {
-    pub fn new(sender: mpsc::UnboundedSender<Event>) -> Self {
-        Self { sender }
+impl<M> EventSender<M> {
+    pub fn new(sender: mpsc::UnboundedSender<Event<M>>) -> Self {
+        Self {
+            sender,
+            tasks: Tasks::default(),
+        }
     }
-    pub fn send(&self, event: Event) {
+    pub fn send(&self, event: Event<M>) {
         // Ignores the result because shutting down the app drops the receiver, which causes the send
         // operation to fail. This is expected behavior and should not panic.
         let _ = self.sender.send(event);
     }
 }
 
-impl From<EventSender> for AppEventSender {
-    fn from(val: EventSender) -> Self {
-        AppEventSender::new(val.sender)
+/// Scheduling, the events are sent by a background task so widgets do not need their own.
+impl<M: Send + 'static> EventSender<M> {
+    /// Sends `event` once after `delay`.
+    pub fn send_after(&self, delay: Duration, event: Event<M>) -> TimerHandle {
+        timer::send_after(&self.sender, delay, event)
+    }
+
+    /// Sends `event` once `deadline` is reached.
+    pub fn send_at(&self, deadline: impl Into<Instant>, event: Event<M>) -> TimerHandle {
+        timer::send_at(&self.sender, deadline.into(), event)
+    }
+
+    /// Sends `event` every `period` until the returned handle is cancelled.
+    ///
+    /// Periods shorter than a millisecond, including zero, are raised to one millisecond.
+    pub fn send_every(&self, period: Duration, event: Event<M>) -> TimerHandle
+    where
+        M: Clone,
+    {
+        timer::send_every(&self.sender, period, event)
+    }
+}
+
+impl<M> From<EventSender<M>> for AppEventSender<M> {
+    fn from(val: EventSender<M>) -> Self {
+        AppEventSender {
+            sender: val.sender,
+            tasks: val.tasks,
+        }
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct AppEventSender {
-    sender: mpsc::UnboundedSender<Event>,
+#[derive(Debug)]
+pub struct AppEventSender<M = ()> {
+    sender: mpsc::UnboundedSender<Event<M>>,
+    tasks: Tasks,
+}
+
+// derived Clone would require `M: Clone`
+impl<M> Clone for AppEventSender<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            tasks: self.tasks.clone(),
+        }
+    }
 }
 
-impl AppEventSender {
-    pub fn new(sender: mpsc::UnboundedSender<Event>) -> Self {
-        Self { sender }
+impl<M> AppEventSender<M> {
+    /// A sender that is not connected to an [`EventHandler`], its tasks are not aborted by
+    /// [`EventHandler::stop`].
+    pub fn new(sender: mpsc::UnboundedSender<Event<M>>) -> Self {
+        Self {
+            sender,
+            tasks: Tasks::default(),
+        }
     }
     pub fn send(&self, event: AppEvent) {
         // Ignores the result because shutting down the app drops the receiver, which causes the send
         // operation to fail. This is expected behavior and should not panic.
         let _ = self.sender.send(Event::App(event));
     }
+    pub fn send_message(&self, message: M) {
+        // Ignores the result because shutting down the app drops the receiver, which causes the send
+        // operation to fail. This is expected behavior and should not panic.
+        let _ = self.sender.send(Event::Message(message));
+    }
+
+    /// Sends an [`AppEvent::Request`] and waits up to [`REQUEST_TIMEOUT`] for the reply.
+    ///
+    /// Fails with a [`RequestError`](request::RequestError) if nobody replies in time or the
+    /// request is dropped unanswered.
+    pub async fn request(
+        &self,
+        name: &str,
+        args: impl IntoIterator<Item = any::AnyType>,
+    ) -> color_eyre::Result<any::AnyType> {
+        self.request_timeout(name, args, REQUEST_TIMEOUT).await
+    }
+
+    /// Like [`AppEventSender::request`] but waits up to `timeout`.
+    pub async fn request_timeout(
+        &self,
+        name: &str,
+        args: impl IntoIterator<Item = any::AnyType>,
+        timeout: Duration,
+    ) -> color_eyre::Result<any::AnyType> {
+        let (request, reply) = Request::new(name, args);
+        self.send(AppEvent::Request(request));
+        Ok(request::wait_for_reply(name, reply, timeout).await?)
+    }
+
+    /// Shows `toast` as a notification.
+    pub fn notify(&self, toast: Toast) {
+        self.send(AppEvent::Toast(toast));
+    }
+    pub fn info(&self, message: &str) {
+        self.notify(Toast::info(message));
+    }
+    pub fn warn(&self, message: &str) {
+        self.notify(Toast::warn(message));
+    }
+    pub fn error(&self, message: &str) {
+        self.notify(Toast::error(message));
+    }
+}
+
+/// Scheduling, the events are sent by a background task so widgets do not need their own.
+impl<M: Send + 'static> AppEventSender<M> {
+    /// Spawns background tasks whose results arrive as events, see [`EventHandler::tasks`].
+    pub fn tasks(&self) -> TaskManager<M> {
+        TaskManager::new(self.tasks.clone(), self.sender.clone())
+    }
+
+    /// Sends `event` once after `delay`.
+    pub fn send_after(&self, delay: Duration, event: AppEvent) -> TimerHandle {
+        timer::send_after(&self.sender, delay, Event::App(event))
+    }
+
+    /// Sends `event` once `deadline` is reached.
+    pub fn send_at(&self, deadline: impl Into<Instant>, event: AppEvent) -> TimerHandle {
+        timer::send_at(&self.sender, deadline.into(), Event::App(event))
+    }
+
+    /// Sends `event` every `period` until the returned handle is cancelled.
+    ///
+    /// Periods shorter than a millisecond, including zero, are raised to one millisecond.
+    pub fn send_every(&self, period: Duration, event: AppEvent) -> TimerHandle
+    where
+        M: Clone,
+    {
+        timer::send_every(&self.sender, period, Event::App(event))
+    }
+
+    /// Sends `message` once after `delay`.
+    pub fn send_message_after(&self, delay: Duration, message: M) -> TimerHandle {
+        timer::send_after(&self.sender, delay, Event::Message(message))
+    }
+
+    /// Sends `message` once `deadline` is reached.
+    pub fn send_message_at(&self, deadline: impl Into<Instant>, message: M) -> TimerHandle {
+        timer::send_at(&self.sender, deadline.into(), Event::Message(message))
+    }
+
+    /// Sends `message` every `period` until the returned handle is cancelled.
+    ///
+    /// Periods shorter than a millisecond, including zero, are raised to one millisecond.
+    pub fn send_message_every(&self, period: Duration, message: M) -> TimerHandle
+    where
+        M: Clone,
+    {
+        timer::send_every(&self.sender, period, Event::Message(message))
+    }
 }
 
 /// A thread that handles reading crossterm events and emitting tick events on a regular schedule.
-struct EventTask {
+struct EventTask<M> {
     /// Event sender channel.
-    sender: mpsc::UnboundedSender<Event>,
+    sender: mpsc::UnboundedSender<Event<M>>,
+    keymap: SharedKeymap,
+    resolver: KeyResolver,
+    rates: watch::Receiver<Rates>,
+    source: Box<dyn EventSource>,
+    /// Replaces the rates if set.
+    clock: Option<ManualClock>,
 }
 
 /*
@@ -174,58 +590,140 @@ async fn get_event() -> Option<CrosstermEvent> {
 }
 */
 
-impl EventTask {
-    /// Constructs a new instance of [`EventThread`].
-    fn new(sender: mpsc::UnboundedSender<Event>) -> Self {
-        Self { sender }
-    }
-
+impl<M> EventTask<M> {
     /// Runs the event thread.
     ///
-    /// This function emits tick events at a fixed rate and polls for crossterm events in between.
-    async fn run(self) -> color_eyre::Result<()> {
-        let tick_rate = Duration::from_secs_f64(1.0 / TICK_FPS);
-        let mut reader = crossterm::event::EventStream::new();
-        let mut tick = tokio::time::interval(tick_rate);
+    /// This function emits tick and render events at the configured rates and polls the event
+    /// source in between. Key events are resolved through the keymap before they are sent.
+    ///
+    /// If several events are ready at once they are handled in a fixed order, so scripted runs
+    /// are reproducible.
+    async fn run(mut self) -> color_eyre::Result<()> {
+        let start = Instant::now();
+        let mut last_tick = start;
+        let mut last_render = start;
+        let mut last_input = start;
+        let mut source_done = false;
+        let mut read_errors = 0;
         loop {
-            let tick_delay = tick.tick();
+            let rates = *self.rates.borrow_and_update();
+            let idle_for = last_input.elapsed();
+            // the deadlines are recomputed every iteration, so rate changes apply immediately
+            let tick_delay = async {
+                match self.clock.as_ref() {
+                    Some(clock) => clock.next_tick().await,
+                    None => sleep_after(last_tick, rates.tick_period(idle_for)).await,
+                }
+            };
+            let render_delay = async {
+                match self.clock.as_ref() {
+                    Some(clock) => clock.next_render().await,
+                    None => sleep_after(last_render, rates.render_period(idle_for)).await,
+                }
+            };
             //let crossterm_event = get_event();
-            let crossterm_event = reader.next().fuse();
+            let source = &mut self.source;
+            let crossterm_event = async {
+                if source_done {
+                    std::future::pending().await
+                } else {
+                    source.next_event().await
+                }
+            }
+            .fuse();
+            let sequence_deadline = self.resolver.deadline();
+            let sequence_timeout = async {
+                match sequence_deadline {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => std::future::pending().await,
+                }
+            };
             tokio::select! {
+              biased;
               _ = self.sender.closed() => {
                 break;
               }
               _ = tick_delay => {
+                last_tick = Instant::now();
                 self.send(Event::Tick);
               }
-              Some(Ok(evt)) = crossterm_event => match evt {
-                    crossterm::event::Event::Key(key_event) => {
-                        self.send(handle_key_events(key_event))
+              _ = render_delay => {
+                last_render = Instant::now();
+                self.send(Event::Render);
+              }
+              changed = self.rates.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+              }
+              _ = sequence_timeout => {
+                let resolved = self.resolver.timeout(&self.keymap());
+                self.send_resolved(resolved);
+              }
+              evt = crossterm_event => match evt {
+                Some(Ok(evt)) => {
+                    read_errors = 0;
+                    last_input = Instant::now();
+                    match evt {
+                        crossterm::event::Event::Key(key_event) => {
+                            let resolved = self.resolver.resolve(&self.keymap(), key_event);
+                            self.send_resolved(resolved);
+                        }
+                        _ => self.send(Event::Crossterm(evt))
                     }
-                    _ => self.send(Event::Crossterm(evt))
-                },
+                }
+                // a single read error is retried, a closed terminal would fail every read
+                Some(Err(error)) => {
+                    read_errors += 1;
+                    if read_errors == MAX_READ_ERRORS {
+                        source_done = true;
+                        let message = format!("Stopped reading terminal events: {error}");
+                        self.send(AppEvent::Toast(Toast::error(&message)).into());
+                    }
+                }
+                None => source_done = true,
+              },
             };
         }
         Ok(())
     }
 
     /// Sends an event to the receiver.
-    fn send(&self, event: Event) {
+    fn send(&self, event: Event<M>) {
         // Ignores the result because shutting down the app drops the receiver, which causes the send
         // operation to fail. This is expected behavior and should not panic.
         let _ = self.sender.send(event);
     }
+
+    /// Snapshot of the keymap, so the lock is not held across an await point.
+    fn keymap(&self) -> Keymap {
+        match self.keymap.lock() {
+            Ok(keymap) => keymap.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn send_resolved(&self, resolved: Vec<Resolved>) {
+        for resolved in resolved {
+            self.send(handle_resolved(resolved));
+        }
+    }
+}
+
+/// Sleeps until `period` after `last`, forever if there is no period.
+async fn sleep_after(last: Instant, period: Option<Duration>) {
+    match period {
+        Some(period) => tokio::time::sleep_until(last + period).await,
+        None => std::future::pending().await,
+    }
 }
 
 #[inline]
-fn handle_key_events(key_event: KeyEvent) -> Event {
-    match key_event.code {
-        KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
-            Event::App(AppEvent::Quit)
-        }
-        _ => Event::App(AppEvent::KeyEvent(key_event)),
-        //_ if key_event.is_repeat() => Event::App(AppEvent::KeyEvent(key_event)),
-        //_ => Event::Crossterm(CrosstermEvent::Key(key_event)),
+fn handle_resolved<M>(resolved: Resolved) -> Event<M> {
+    match resolved {
+        Resolved::Action(action) if action == keymap::QUIT_ACTION => Event::App(AppEvent::Quit),
+        Resolved::Action(action) => Event::App(AppEvent::Action(action)),
+        Resolved::Key(key_event) => Event::App(AppEvent::KeyEvent(key_event)),
     }
 }
 
@@ -235,22 +733,135 @@ pub mod test_utils {
 
     ///Object
     #[derive(Debug)]
-    pub struct EventReceiver {
-        receiver: mpsc::UnboundedReceiver<Event>,
+    pub struct EventReceiver<M = ()> {
+        receiver: mpsc::UnboundedReceiver<Event<M>>,
     }
 
-    impl EventReceiver {
-        pub async fn next(&mut self) -> color_eyre::Result<Event> {
+    impl<M> EventReceiver<M> {
+        pub async fn next(&mut self) -> color_eyre::Result<Event<M>> {
             self.receiver
                 .recv()
                 .await
-                .ok_or_eyre("Failed to receive event")
+                .ok_or_else(|| eyre!("Failed to receive event"))
         }
     }
 
     /// Used for testing only
-    pub fn dummy_event_sender() -> (EventSender, EventReceiver) {
+    pub fn dummy_event_sender<M>() -> (EventSender<M>, EventReceiver<M>) {
         let (tx, rx) = mpsc::unbounded_channel();
-        (EventSender { sender: tx }, EventReceiver { receiver: rx })
+        (EventSender::new(tx), EventReceiver { receiver: rx })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::test_utils::dummy_event_sender;
+    use super::*;
+    use crate::componets::toast::ToastLevel;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, PartialEq)]
+    enum Msg {
+        Save(u32),
+    }
+
+    #[tokio::test]
+    async fn typed_messages() {
+        let (sender, mut receiver) = dummy_event_sender::<Msg>();
+        let app_sender: AppEventSender<Msg> = sender.clone().into();
+        app_sender.send_message(Msg::Save(42));
+        sender.send(AppEvent::Quit.into());
+        assert!(matches!(
+            receiver.next().await.unwrap(),
+            Event::Message(Msg::Save(42))
+        ));
+        assert!(matches!(
+            receiver.next().await.unwrap(),
+            Event::App(AppEvent::Quit)
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn headless_run() {
+        use crate::events::source::{ManualClock, ScriptedSource};
+        use crossterm::event::KeyCode;
+
+        let mut keymap = Keymap::default();
+        keymap.bind(keymap::DEFAULT_MODE, "g g", "top").unwrap();
+        let clock = ManualClock::new();
+        let source = ScriptedSource::new()
+            .wait(Duration::from_millis(10))
+            .text("gg")
+            .wait(Duration::from_secs(5))
+            .key(KeyCode::Char('x'));
+        let mut events: EventHandler = EventHandler::builder()
+            .keymap(keymap)
+            .source(source)
+            .manual_clock(clock.clone())
+            .build();
+        clock.tick();
+
+        assert!(matches!(events.next().await.unwrap(), Event::Tick));
+        assert!(matches!(
+            events.next().await.unwrap(),
+            Event::App(AppEvent::Action(action)) if action == "top"
+        ));
+        assert!(matches!(
+            events.next().await.unwrap(),
+            Event::App(AppEvent::KeyEvent(key)) if key.code == KeyCode::Char('x')
+        ));
+        events.stop();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn failed_recording_keeps_events() {
+        use crate::events::source::ScriptedSource;
+
+        let mut events: EventHandler = EventHandler::builder()
+            .source(ScriptedSource::new().key(crossterm::event::KeyCode::Char('q')))
+            .manual_clock(ManualClock::new())
+            .record("/dev/full")
+            .unwrap()
+            .build();
+        assert!(matches!(
+            events.next().await.unwrap(),
+            Event::App(AppEvent::KeyEvent(_))
+        ));
+        assert!(matches!(
+            events.next().await.unwrap(),
+            Event::App(AppEvent::Toast(_))
+        ));
+        assert!(events.recorder.is_none());
+        events.stop();
+    }
+
+    /// Fails every read, like a terminal that went away.
+    struct BrokenSource(Arc<AtomicU32>);
+
+    impl EventSource for BrokenSource {
+        fn next_event(
+            &mut self,
+        ) -> futures::future::BoxFuture<'_, Option<std::io::Result<CrosstermEvent>>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            let error = std::io::Error::from(std::io::ErrorKind::BrokenPipe);
+            std::future::ready(Some(Err(error))).boxed()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn read_errors_stop_the_source() {
+        let reads = Arc::new(AtomicU32::new(0));
+        let mut events: EventHandler = EventHandler::builder()
+            .source(BrokenSource(reads.clone()))
+            .manual_clock(ManualClock::new())
+            .build();
+        let Event::App(AppEvent::Toast(toast)) = events.next().await.unwrap() else {
+            panic!("not a toast");
+        };
+        assert_eq!(toast.level, ToastLevel::Error);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(reads.load(Ordering::SeqCst), MAX_READ_ERRORS);
+        events.stop();
     }
 }
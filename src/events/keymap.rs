@@ -0,0 +1,459 @@
+use crate::componets::toast::{DISMISS_ACTION, HISTORY_ACTION};
+use color_eyre::eyre::{bail, eyre};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Mode that is active when nothing else was selected.
+pub const DEFAULT_MODE: &str = "normal";
+/// Bindings in this mode apply in every mode, unless the active mode binds the same keys.
+pub const GLOBAL_MODE: &str = "global";
+/// Action that gets turned into [`AppEvent::Quit`](crate::events::AppEvent::Quit).
+pub const QUIT_ACTION: &str = "quit";
+/// How long to wait for the next key of a sequence.
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// A keymap shared between the event task and the application.
+pub type SharedKeymap = Arc<Mutex<Keymap>>;
+
+/// A single key press together with its modifiers, e.g. `ctrl+x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers = modifiers;
+        // the terminal already reports the shifted character, so shift carries no information
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self { code, modifiers }
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(event: KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = color_eyre::eyre::Error;
+
+    /// Parses chords like `g`, `G`, `ctrl+x`, `alt+shift+f1` or `enter`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (modifier_part, key) = match s.strip_suffix("++") {
+            Some(rest) => (rest, "+"),
+            None => match s.rsplit_once('+') {
+                Some((modifiers, key)) => (modifiers, key),
+                None => ("", s),
+            },
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in modifier_part.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                "super" => KeyModifiers::SUPER,
+                "meta" => KeyModifiers::META,
+                "hyper" => KeyModifiers::HYPER,
+                _ => bail!("unknown modifier `{modifier}` in `{s}`"),
+            };
+        }
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => parse_key_name(key).ok_or_else(|| eyre!("unknown key `{key}` in `{s}`"))?,
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+fn parse_key_name(name: &str) -> Option<KeyCode> {
+    let name = name.to_lowercase();
+    let code = match name.as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "space" => KeyCode::Char(' '),
+        "plus" => KeyCode::Char('+'),
+        _ => KeyCode::F(name.strip_prefix('f')?.parse().ok()?),
+    };
+    Some(code)
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl"),
+            (KeyModifiers::ALT, "alt"),
+            (KeyModifiers::SHIFT, "shift"),
+            (KeyModifiers::SUPER, "super"),
+            (KeyModifiers::META, "meta"),
+            (KeyModifiers::HYPER, "hyper"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char('+') => write!(f, "plus"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            code => write!(f, "{}", code.to_string().to_lowercase()),
+        }
+    }
+}
+
+/// Parses a whitespace separated sequence of chords, e.g. `g g` or `ctrl+x ctrl+s`.
+pub fn parse_sequence(keys: &str) -> color_eyre::Result<Vec<KeyChord>> {
+    let sequence = keys
+        .split_whitespace()
+        .map(KeyChord::from_str)
+        .collect::<color_eyre::Result<Vec<_>>>()?;
+    if sequence.is_empty() {
+        bail!("empty key sequence");
+    }
+    Ok(sequence)
+}
+
+/// Maps key chords and sequences of chords to named actions.
+///
+/// Every mode has its own table, the [`GLOBAL_MODE`] table is consulted when the active mode has
+/// no binding for the pressed keys.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    modes: HashMap<String, HashMap<Vec<KeyChord>, String>>,
+    mode: String,
+    timeout: Duration,
+}
+
+impl Default for Keymap {
+    /// A keymap that binds `ctrl+c` to [`QUIT_ACTION`], `alt+x` to [`DISMISS_ACTION`] and
+    /// `alt+n` to [`HISTORY_ACTION`].
+    fn default() -> Self {
+        let mut keymap = Self::empty();
+        let bindings = [
+            ('c', KeyModifiers::CONTROL, QUIT_ACTION),
+            ('x', KeyModifiers::ALT, DISMISS_ACTION),
+            ('n', KeyModifiers::ALT, HISTORY_ACTION),
+        ];
+        for (c, modifiers, action) in bindings {
+            let chord = KeyChord::new(KeyCode::Char(c), modifiers);
+            keymap.bind_sequence(GLOBAL_MODE, vec![chord], action);
+        }
+        keymap
+    }
+}
+
+impl Keymap {
+    /// A keymap without any bindings.
+    pub fn empty() -> Self {
+        Self {
+            modes: HashMap::new(),
+            mode: String::from(DEFAULT_MODE),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn shared(self) -> SharedKeymap {
+        Arc::new(Mutex::new(self))
+    }
+
+    /// Sets how long to wait for the next key of an unfinished sequence.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub fn get_timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Binds `keys` (see [`parse_sequence`]) to `action` in `mode`.
+    pub fn bind(&mut self, mode: &str, keys: &str, action: &str) -> color_eyre::Result<()> {
+        let sequence = parse_sequence(keys)?;
+        self.bind_sequence(mode, sequence, action);
+        Ok(())
+    }
+
+    pub fn bind_sequence(&mut self, mode: &str, sequence: Vec<KeyChord>, action: &str) {
+        self.modes
+            .entry(String::from(mode))
+            .or_default()
+            .insert(sequence, String::from(action));
+    }
+
+    /// Removes the binding of `keys` in `mode`.
+    pub fn unbind(&mut self, mode: &str, keys: &str) -> color_eyre::Result<()> {
+        let sequence = parse_sequence(keys)?;
+        self.unbind_sequence(mode, &sequence);
+        Ok(())
+    }
+
+    pub fn unbind_sequence(&mut self, mode: &str, sequence: &[KeyChord]) {
+        if let Some(table) = self.modes.get_mut(mode) {
+            table.remove(sequence);
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: &str) {
+        self.mode = String::from(mode);
+    }
+
+    pub fn mode(&self) -> &str {
+        &self.mode
+    }
+
+    /// Looks up `sequence` in the active mode, then in the global one.
+    fn lookup(&self, sequence: &[KeyChord]) -> Lookup<'_> {
+        let mut result = Lookup::default();
+        for mode in [self.mode.as_str(), GLOBAL_MODE] {
+            let Some(table) = self.modes.get(mode) else {
+                continue;
+            };
+            for (keys, action) in table {
+                if keys.as_slice() == sequence {
+                    result.exact = result.exact.or(Some(action.as_str()));
+                } else if keys.starts_with(sequence) {
+                    result.prefix = true;
+                }
+            }
+            if result.exact.is_some() {
+                break;
+            }
+        }
+        result
+    }
+}
+
+#[derive(Debug, Default)]
+struct Lookup<'a> {
+    /// Action bound to exactly this sequence.
+    exact: Option<&'a str>,
+    /// Whether longer sequences start with this one.
+    prefix: bool,
+}
+
+/// Result of feeding a key into a [`KeyResolver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolved {
+    /// The keys completed a binding.
+    Action(String),
+    /// The key is not bound and is passed on as is.
+    Key(KeyEvent),
+}
+
+/// Tracks the keys of a sequence that is still being typed.
+#[derive(Debug, Default)]
+pub struct KeyResolver {
+    /// The presses of the sequence and the releases and repeats that arrived in between.
+    pending: Vec<KeyEvent>,
+    deadline: Option<Instant>,
+}
+
+impl KeyResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Point in time at which [`KeyResolver::timeout`] has to be called.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Feeds a key event into the resolver.
+    ///
+    /// Returns an empty list as long as the keys are the prefix of a longer binding. Releases
+    /// and repeats are never part of a binding, while a sequence is pending they are held back
+    /// and passed on in order once it resolves.
+    pub fn resolve(&mut self, keymap: &Keymap, event: KeyEvent) -> Vec<Resolved> {
+        if event.kind != KeyEventKind::Press {
+            if self.pending.is_empty() {
+                return vec![Resolved::Key(event)];
+            }
+            self.pending.push(event);
+            return Vec::new();
+        }
+        let mut sequence = self.sequence();
+        sequence.push(event.into());
+        let lookup = keymap.lookup(&sequence);
+        if lookup.prefix {
+            self.pending.push(event);
+            self.deadline = Some(Instant::now() + keymap.timeout);
+            return Vec::new();
+        }
+        if let Some(action) = lookup.exact {
+            return self.finish(action);
+        }
+        if self.pending.is_empty() {
+            return vec![Resolved::Key(event)];
+        }
+        // the sequence went nowhere, give up on it and start over with this key
+        let mut resolved = self.flush();
+        resolved.extend(self.resolve(keymap, event));
+        resolved
+    }
+
+    /// Finishes an unfinished sequence once the timeout elapsed.
+    ///
+    /// If the typed keys are bound themselves their action is emitted, otherwise the keys are
+    /// passed on.
+    pub fn timeout(&mut self, keymap: &Keymap) -> Vec<Resolved> {
+        if let Some(action) = keymap.lookup(&self.sequence()).exact {
+            return self.finish(action);
+        }
+        self.flush()
+    }
+
+    /// The chords of the pending presses.
+    fn sequence(&self) -> Vec<KeyChord> {
+        self.pending
+            .iter()
+            .filter(|e| e.kind == KeyEventKind::Press)
+            .map(|&e| e.into())
+            .collect()
+    }
+
+    /// Emits `action` for the pending presses, followed by the held back releases and repeats.
+    fn finish(&mut self, action: &str) -> Vec<Resolved> {
+        self.deadline = None;
+        let held = self
+            .pending
+            .drain(..)
+            .filter(|e| e.kind != KeyEventKind::Press);
+        std::iter::once(Resolved::Action(String::from(action)))
+            .chain(held.map(Resolved::Key))
+            .collect()
+    }
+
+    fn flush(&mut self) -> Vec<Resolved> {
+        self.deadline = None;
+        self.pending.drain(..).map(Resolved::Key).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parse_chords() {
+        let chord: KeyChord = "ctrl+x".parse().unwrap();
+        assert_eq!(
+            chord,
+            KeyChord::new(KeyCode::Char('x'), KeyModifiers::CONTROL)
+        );
+        let chord: KeyChord = "shift+g".parse().unwrap();
+        assert_eq!(chord, KeyChord::new(KeyCode::Char('G'), KeyModifiers::NONE));
+        let chord: KeyChord = "alt+F5".parse().unwrap();
+        assert_eq!(chord, KeyChord::new(KeyCode::F(5), KeyModifiers::ALT));
+        assert_eq!(
+            "ctrl++".parse::<KeyChord>().unwrap().to_string(),
+            "ctrl+plus"
+        );
+        assert!("hyperspace".parse::<KeyChord>().is_err());
+        assert!("foo+a".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn resolve_sequences() {
+        let mut keymap = Keymap::default();
+        keymap.bind(DEFAULT_MODE, "g g", "top").unwrap();
+        keymap.bind(DEFAULT_MODE, "ctrl+x ctrl+s", "save").unwrap();
+        keymap.bind("insert", "esc", "leave").unwrap();
+        let mut resolver = KeyResolver::new();
+        let g = press(KeyCode::Char('g'), KeyModifiers::NONE);
+        let x = press(KeyCode::Char('x'), KeyModifiers::NONE);
+
+        assert!(resolver.resolve(&keymap, g).is_empty());
+        assert!(resolver.deadline().is_some());
+        assert_eq!(
+            resolver.resolve(&keymap, g),
+            vec![Resolved::Action(String::from("top"))]
+        );
+
+        let ctrl_x = press(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        let ctrl_s = press(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert!(resolver.resolve(&keymap, ctrl_x).is_empty());
+        assert_eq!(
+            resolver.resolve(&keymap, ctrl_s),
+            vec![Resolved::Action(String::from("save"))]
+        );
+
+        // an abandoned sequence is passed on as keys
+        assert!(resolver.resolve(&keymap, g).is_empty());
+        assert_eq!(
+            resolver.resolve(&keymap, x),
+            vec![Resolved::Key(g), Resolved::Key(x)]
+        );
+        assert!(resolver.resolve(&keymap, g).is_empty());
+        assert_eq!(resolver.timeout(&keymap), vec![Resolved::Key(g)]);
+
+        // releases are held back until the sequence resolves, never dropped
+        let mut release = g;
+        release.kind = KeyEventKind::Release;
+        assert_eq!(
+            resolver.resolve(&keymap, release),
+            vec![Resolved::Key(release)]
+        );
+        assert!(resolver.resolve(&keymap, g).is_empty());
+        assert!(resolver.resolve(&keymap, release).is_empty());
+        assert_eq!(
+            resolver.resolve(&keymap, g),
+            vec![
+                Resolved::Action(String::from("top")),
+                Resolved::Key(release)
+            ]
+        );
+        assert!(resolver.resolve(&keymap, g).is_empty());
+        assert!(resolver.resolve(&keymap, release).is_empty());
+        assert_eq!(
+            resolver.resolve(&keymap, x),
+            vec![Resolved::Key(g), Resolved::Key(release), Resolved::Key(x)]
+        );
+
+        // global bindings apply in every mode
+        keymap.set_mode("insert");
+        let ctrl_c = press(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(
+            resolver.resolve(&keymap, ctrl_c),
+            vec![Resolved::Action(String::from(QUIT_ACTION))]
+        );
+        assert_eq!(resolver.resolve(&keymap, g), vec![Resolved::Key(g)]);
+    }
+}
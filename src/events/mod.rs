@@ -1,3 +1,11 @@
 pub mod any;
+pub mod config;
 pub mod event;
+pub mod keymap;
+pub mod rate;
+pub mod record;
+pub mod request;
+pub mod source;
+pub mod task;
+pub mod timer;
 pub use event::*;
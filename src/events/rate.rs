@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+/// The frequency at which tick events are emitted by default.
+pub const TICK_FPS: f64 = 30.0; // 1.0;
+
+/// Shortest time between two events, faster rates are clamped to it.
+const MIN_PERIOD: Duration = Duration::from_millis(1);
+/// Longest time between two events, slower rates are clamped to it.
+const MAX_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Lower rate used once the application is idle, see [`Rates::adaptive`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Idle {
+    /// Time without input after which the application counts as idle.
+    pub after: Duration,
+    /// Events per second while idle.
+    pub fps: f64,
+}
+
+/// Rates at which the event task emits [`Event::Tick`](crate::events::Event::Tick) and
+/// [`Event::Render`](crate::events::Event::Render).
+///
+/// Rates that are zero, negative or NaN disable the events, the others are clamped to between
+/// one event per day and one per millisecond.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rates {
+    /// Ticks per second.
+    pub tick_fps: f64,
+    /// Render events per second, `None` disables them.
+    pub render_fps: Option<f64>,
+    /// Whether ticks are paused, render events are not affected.
+    pub paused: bool,
+    /// Rate to drop to when there was no input for a while.
+    pub idle: Option<Idle>,
+}
+
+impl Default for Rates {
+    fn default() -> Self {
+        Self {
+            tick_fps: TICK_FPS,
+            render_fps: None,
+            paused: false,
+            idle: None,
+        }
+    }
+}
+
+impl Rates {
+    /// Drops both rates to `idle_fps` once there was no input for `idle_after`.
+    ///
+    /// The full rates apply again with the next input.
+    pub fn adaptive(mut self, idle_after: Duration, idle_fps: f64) -> Self {
+        self.idle = Some(Idle {
+            after: idle_after,
+            fps: idle_fps,
+        });
+        self
+    }
+
+    /// Time between two ticks after `idle_for` without input, `None` if ticks are disabled.
+    pub fn tick_period(&self, idle_for: Duration) -> Option<Duration> {
+        if self.paused {
+            return None;
+        }
+        self.period(self.tick_fps, idle_for)
+    }
+
+    /// Time between two render events after `idle_for` without input, `None` if they are
+    /// disabled.
+    pub fn render_period(&self, idle_for: Duration) -> Option<Duration> {
+        self.period(self.render_fps?, idle_for)
+    }
+
+    fn period(&self, fps: f64, idle_for: Duration) -> Option<Duration> {
+        let fps = match self.idle {
+            Some(idle) if idle_for >= idle.after => fps.min(idle.fps),
+            _ => fps,
+        };
+        // infinite rates give a zero period, tiny ones overflow
+        (fps > 0.0).then(|| {
+            Duration::try_from_secs_f64(1.0 / fps)
+                .unwrap_or(MAX_PERIOD)
+                .clamp(MIN_PERIOD, MAX_PERIOD)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn adaptive_periods() {
+        let mut rates = Rates {
+            render_fps: Some(60.0),
+            ..Rates::default()
+        }
+        .adaptive(Duration::from_secs(5), 2.0);
+        let active = Duration::from_secs(1);
+        let idle = Duration::from_secs(10);
+        assert_eq!(
+            rates.tick_period(active),
+            Some(Duration::from_secs_f64(1.0 / TICK_FPS))
+        );
+        assert_eq!(rates.tick_period(idle), Some(Duration::from_millis(500)));
+        assert_eq!(rates.render_period(idle), Some(Duration::from_millis(500)));
+        rates.paused = true;
+        assert_eq!(rates.tick_period(active), None);
+        assert!(rates.render_period(active).is_some());
+    }
+
+    #[test]
+    fn periods_are_clamped() {
+        let period = |fps| {
+            Rates {
+                tick_fps: fps,
+                ..Rates::default()
+            }
+            .tick_period(Duration::ZERO)
+        };
+        assert_eq!(period(f64::INFINITY), Some(MIN_PERIOD));
+        assert_eq!(period(1e6), Some(MIN_PERIOD));
+        assert_eq!(period(1e-300), Some(MAX_PERIOD));
+        assert_eq!(period(f64::MIN_POSITIVE), Some(MAX_PERIOD));
+        assert_eq!(period(0.0), None);
+        assert_eq!(period(-1.0), None);
+        assert_eq!(period(f64::NAN), None);
+    }
+}
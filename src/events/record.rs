@@ -0,0 +1,206 @@
+use crate::events::Event;
+use color_eyre::eyre::WrapErr;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, LineWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// Recorded events with their time relative to the start of the recording.
+pub type Recording<M> = Vec<(Duration, Event<M>)>;
+
+/// One line of a recording.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry<E> {
+    /// Milliseconds since the recording started.
+    at_ms: u64,
+    event: E,
+}
+
+/// How fast a recording is played back, see
+/// [`EventHandlerBuilder::replay`](crate::events::EventHandlerBuilder::replay).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReplaySpeed {
+    /// Keeps the time between the events as recorded.
+    #[default]
+    RealTime,
+    /// Sends all events right away, for regression tests.
+    AsFastAsPossible,
+}
+
+/// Writes events as JSON lines with their time relative to the start of the recording.
+///
+/// Application messages are not recorded, the application sends them again when a recording is
+/// replayed into it.
+#[derive(Debug)]
+pub struct Recorder {
+    writer: LineWriter<File>,
+    start: Instant,
+}
+
+impl Recorder {
+    /// Starts a new recording in `path`, an existing file is overwritten.
+    pub fn create(path: impl AsRef<Path>) -> color_eyre::Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .wrap_err_with(|| format!("failed to create recording {}", path.display()))?;
+        Ok(Self {
+            writer: LineWriter::new(file),
+            start: Instant::now(),
+        })
+    }
+
+    /// Appends `event` to the recording.
+    ///
+    /// Messages and events that cannot be serialized, e.g. custom events with
+    /// [`AnyCustom`](crate::events::any::AnyType::AnyCustom) arguments, are skipped.
+    pub fn record<M>(&mut self, event: &Event<M>) -> std::io::Result<()> {
+        let event: Event = match event {
+            Event::Tick => Event::Tick,
+            Event::Render => Event::Render,
+            Event::Crossterm(event) => Event::Crossterm(event.clone()),
+            Event::App(event) => Event::App(event.clone()),
+            Event::Message(_) => return Ok(()),
+        };
+        let Ok(event) = serde_json::to_string(&event) else {
+            return Ok(());
+        };
+        let at_ms = self.start.elapsed().as_millis() as u64;
+        writeln!(self.writer, r#"{{"at_ms":{at_ms},"event":{event}}}"#)
+    }
+}
+
+/// Reads a recording written by a [`Recorder`].
+pub fn read_recording<M: DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> color_eyre::Result<Recording<M>> {
+    let path = path.as_ref();
+    let file = File::open(path)
+        .wrap_err_with(|| format!("failed to open recording {}", path.display()))?;
+    let mut events = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: Entry<Event<M>> = serde_json::from_str(&line)
+            .wrap_err_with(|| format!("{}:{}: invalid event", path.display(), index + 1))?;
+        events.push((Duration::from_millis(entry.at_ms), entry.event));
+    }
+    Ok(events)
+}
+
+/// Sends the recorded `events` into `sender`.
+pub(crate) async fn replay<M>(
+    sender: mpsc::UnboundedSender<Event<M>>,
+    events: Recording<M>,
+    speed: ReplaySpeed,
+) -> color_eyre::Result<()> {
+    let start = Instant::now();
+    for (at, event) in events {
+        if speed == ReplaySpeed::RealTime {
+            tokio::time::sleep_until(start + at).await;
+        }
+        if sender.send(event).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::componets::toast::Toast;
+    use crate::events::source::{ManualClock, ScriptedSource};
+    use crate::events::{AppEvent, EventHandler};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum Msg {
+        Open(String),
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn record_and_replay() {
+        let path =
+            std::env::temp_dir().join(format!("persistent-tui-{}.jsonl", std::process::id()));
+        let mut recorder = Recorder::create(&path).unwrap();
+        let key = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        recorder.record(&Event::<Msg>::Tick).unwrap();
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        recorder
+            .record::<Msg>(&AppEvent::KeyEvent(key).into())
+            .unwrap();
+        recorder
+            .record(&Event::Message(Msg::Open("a.txt".into())))
+            .unwrap();
+        recorder.record::<Msg>(&AppEvent::Quit.into()).unwrap();
+        drop(recorder);
+
+        // the message is left out
+        let recording = read_recording::<Msg>(&path).unwrap();
+        assert_eq!(recording.len(), 3);
+        assert_eq!(recording[1].0, Duration::from_millis(250));
+
+        let mut events: EventHandler<Msg> = EventHandler::builder()
+            .replay(&path, ReplaySpeed::RealTime)
+            .unwrap()
+            .build();
+        let start = Instant::now();
+        assert!(matches!(events.next().await.unwrap(), Event::Tick));
+        assert!(matches!(
+            events.next().await.unwrap(),
+            Event::App(AppEvent::KeyEvent(k)) if k == key
+        ));
+        assert_eq!(start.elapsed(), Duration::from_millis(250));
+        assert!(matches!(
+            events.next().await.unwrap(),
+            Event::App(AppEvent::Quit)
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn only_inputs_are_recorded() {
+        let path = std::env::temp_dir().join(format!(
+            "persistent-tui-inputs-{}.jsonl",
+            std::process::id()
+        ));
+        let key = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        let mut events: EventHandler<Msg> = EventHandler::builder()
+            .source(
+                ScriptedSource::new()
+                    .wait(Duration::from_millis(10))
+                    .key_event(key),
+            )
+            .manual_clock(ManualClock::new())
+            .record(&path)
+            .unwrap()
+            .build();
+        events.send_message(Msg::Open("a.txt".into()));
+        events.send(AppEvent::Toast(Toast::info("opened")));
+        assert!(matches!(events.next().await.unwrap(), Event::Message(_)));
+        assert!(matches!(
+            events.next().await.unwrap(),
+            Event::App(AppEvent::Toast(_))
+        ));
+        assert!(matches!(
+            events.next().await.unwrap(),
+            Event::App(AppEvent::KeyEvent(k)) if k == key
+        ));
+        events.stop();
+        events.stop_recording();
+
+        let recording = read_recording::<Msg>(&path).unwrap();
+        assert_eq!(recording.len(), 1);
+        assert!(matches!(
+            recording[0].1,
+            Event::App(AppEvent::KeyEvent(k)) if k == key
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,142 @@
+use crate::events::any::AnyType;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// How long [`AppEventSender::request`](crate::events::AppEventSender::request) waits for the
+/// reply.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+type ReplySlot = Arc<Mutex<Option<oneshot::Sender<AnyType>>>>;
+
+/// A question sent to the main loop as [`AppEvent::Request`](crate::events::AppEvent::Request),
+/// answer it with [`Request::reply`].
+///
+/// The reply channel is not serialized, a recorded request can not be answered.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Request {
+    pub name: String,
+    pub args: Option<Arc<[AnyType]>>,
+    /// Shared so the event stays `Clone`, the first reply takes the sender.
+    #[serde(skip)]
+    reply: ReplySlot,
+}
+
+impl Request {
+    pub(crate) fn new(
+        name: &str,
+        args: impl IntoIterator<Item = AnyType>,
+    ) -> (Self, oneshot::Receiver<AnyType>) {
+        let (sender, receiver) = oneshot::channel();
+        let args: Arc<[AnyType]> = args.into_iter().collect();
+        let request = Self {
+            name: String::from(name),
+            args: (!args.is_empty()).then_some(args),
+            reply: Arc::new(Mutex::new(Some(sender))),
+        };
+        (request, receiver)
+    }
+
+    pub fn arg(&self, index: usize) -> Option<&AnyType> {
+        self.args.as_ref()?.get(index)
+    }
+
+    /// Answers the request, returns `false` if it was already answered or the asker gave up.
+    pub fn reply(&self, value: impl Into<AnyType>) -> bool {
+        let sender = match self.reply.lock() {
+            Ok(mut reply) => reply.take(),
+            Err(poisoned) => poisoned.into_inner().take(),
+        };
+        sender.is_some_and(|sender| sender.send(value.into()).is_ok())
+    }
+
+    /// Whether the request can still be answered.
+    pub fn is_pending(&self) -> bool {
+        self.reply
+            .lock()
+            .map(|reply| reply.as_ref().is_some_and(|sender| !sender.is_closed()))
+            .unwrap_or(false)
+    }
+}
+
+/// Why a request got no reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestError {
+    /// No reply arrived in time.
+    Timeout(String, Duration),
+    /// Every copy of the request was dropped without a reply, e.g. because the app does not
+    /// handle it or quit.
+    Unanswered(String),
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::Timeout(name, timeout) => {
+                write!(f, "request `{name}` got no reply within {timeout:?}")
+            }
+            RequestError::Unanswered(name) => write!(f, "request `{name}` was not answered"),
+        }
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+/// Waits up to `timeout` for the reply to the request called `name`.
+pub(crate) async fn wait_for_reply(
+    name: &str,
+    reply: oneshot::Receiver<AnyType>,
+    timeout: Duration,
+) -> Result<AnyType, RequestError> {
+    match tokio::time::timeout(timeout, reply).await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(_)) => Err(RequestError::Unanswered(String::from(name))),
+        Err(_) => Err(RequestError::Timeout(String::from(name), timeout)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::test_utils::dummy_event_sender;
+    use crate::events::{AppEvent, AppEventSender, Event};
+
+    #[tokio::test(start_paused = true)]
+    async fn requests_round_trip() {
+        let (sender, mut receiver) = dummy_event_sender::<()>();
+        let sender: AppEventSender = sender.into();
+
+        let asker = sender.clone();
+        let taken = tokio::spawn(async move { asker.request("taken", ["bob".into()]).await });
+        let Event::App(AppEvent::Request(request)) = receiver.next().await.unwrap() else {
+            panic!("not a request");
+        };
+        assert!(matches!(request.arg(0), Some(AnyType::AnyString(name)) if name == "bob"));
+        let copy = request.clone();
+        assert!(request.reply(1u8));
+        assert!(!copy.reply(0u8));
+        assert!(matches!(taken.await.unwrap(), Ok(AnyType::AnyInt(1))));
+
+        let asker = sender.clone();
+        let ignored = tokio::spawn(async move { asker.request("load", [42u32.into()]).await });
+        drop(receiver.next().await.unwrap());
+        let error = ignored.await.unwrap().unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<RequestError>(),
+            Some(&RequestError::Unanswered(String::from("load")))
+        );
+
+        let slow = sender.request_timeout("load", [], Duration::from_millis(10));
+        let (error, event) = tokio::join!(slow, receiver.next());
+        assert!(matches!(
+            error.unwrap_err().downcast_ref::<RequestError>(),
+            Some(RequestError::Timeout(..))
+        ));
+        let Ok(Event::App(AppEvent::Request(request))) = event else {
+            panic!("not a request");
+        };
+        assert!(!request.is_pending());
+    }
+}
@@ -0,0 +1,169 @@
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
+use futures::future::BoxFuture;
+use futures::{FutureExt, StreamExt};
+use std::collections::VecDeque;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+
+/// Where the event task reads terminal events from.
+///
+/// The event task drops the returned future whenever another event arrives first, so
+/// implementations must not lose events when that happens.
+pub trait EventSource: Send + 'static {
+    /// Waits for the next event, `None` once the source is exhausted.
+    ///
+    /// A failed read is retried, after three failures in a row the source is no longer read and
+    /// the error is reported with an error toast.
+    fn next_event(&mut self) -> BoxFuture<'_, Option<io::Result<CrosstermEvent>>>;
+}
+
+/// Reads events from the terminal, this is the default source.
+///
+/// The source does not change the terminal. Pastes only arrive as a single
+/// [`CrosstermEvent::Paste`] once bracketed paste is enabled, which the
+/// [`Runner`](crate::app::Runner) and [`setup`](crate::app::setup) do.
+pub struct CrosstermSource {
+    reader: crossterm::event::EventStream,
+}
+
+impl Default for CrosstermSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrosstermSource {
+    pub fn new() -> Self {
+        Self {
+            reader: crossterm::event::EventStream::new(),
+        }
+    }
+}
+
+impl EventSource for CrosstermSource {
+    fn next_event(&mut self) -> BoxFuture<'_, Option<io::Result<CrosstermEvent>>> {
+        self.reader.next().boxed()
+    }
+}
+
+/// Emits a fixed list of events at fixed offsets, for tests and headless runs.
+///
+/// The offsets are measured from the first call to [`EventSource::next_event`], so the timing
+/// does not depend on how busy the event task is. With tokio's paused clock the timing is fully
+/// reproducible.
+#[derive(Debug, Default, Clone)]
+pub struct ScriptedSource {
+    start: Option<Instant>,
+    /// Offset of the next event that gets added.
+    cursor: Duration,
+    events: VecDeque<(Duration, CrosstermEvent)>,
+}
+
+impl ScriptedSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delays all following events by `delay`.
+    pub fn wait(mut self, delay: Duration) -> Self {
+        self.cursor += delay;
+        self
+    }
+
+    pub fn event(mut self, event: CrosstermEvent) -> Self {
+        self.events.push_back((self.cursor, event));
+        self
+    }
+
+    /// Adds a press of `code` without modifiers.
+    pub fn key(self, code: KeyCode) -> Self {
+        self.key_event(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    pub fn key_event(self, key_event: KeyEvent) -> Self {
+        self.event(CrosstermEvent::Key(key_event))
+    }
+
+    /// Adds a bracketed paste of `text`.
+    pub fn paste(self, text: &str) -> Self {
+        self.event(CrosstermEvent::Paste(text.to_string()))
+    }
+
+    /// Adds a press of every character in `text`.
+    pub fn text(self, text: &str) -> Self {
+        text.chars()
+            .fold(self, |source, c| source.key(KeyCode::Char(c)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl EventSource for ScriptedSource {
+    fn next_event(&mut self) -> BoxFuture<'_, Option<io::Result<CrosstermEvent>>> {
+        async move {
+            let start = *self.start.get_or_insert_with(Instant::now);
+            let (offset, _) = self.events.front()?;
+            tokio::time::sleep_until(start + *offset).await;
+            // only take the event once it is due, the future may be dropped while sleeping
+            self.events.pop_front().map(|(_, event)| Ok(event))
+        }
+        .boxed()
+    }
+}
+
+/// Emits ticks and render events on request instead of on a timer.
+///
+/// Pass it to [`EventHandlerBuilder::manual_clock`](crate::events::EventHandlerBuilder::manual_clock),
+/// every call to [`ManualClock::tick`] then results in exactly one
+/// [`Event::Tick`](crate::events::Event::Tick).
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    ticks: Arc<Semaphore>,
+    renders: Arc<Semaphore>,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            ticks: Arc::new(Semaphore::new(0)),
+            renders: Arc::new(Semaphore::new(0)),
+        }
+    }
+
+    /// Emits one tick.
+    pub fn tick(&self) {
+        self.ticks.add_permits(1);
+    }
+
+    /// Emits one render event.
+    pub fn render(&self) {
+        self.renders.add_permits(1);
+    }
+
+    pub(crate) async fn next_tick(&self) {
+        Self::take(&self.ticks).await
+    }
+
+    pub(crate) async fn next_render(&self) {
+        Self::take(&self.renders).await
+    }
+
+    async fn take(semaphore: &Semaphore) {
+        match semaphore.acquire().await {
+            Ok(permit) => permit.forget(),
+            // the semaphore is never closed
+            Err(_) => std::future::pending().await,
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use crate::events::{AppEvent, Event};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+
+/// Identifies a task spawned with [`TaskManager::spawn`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TaskId(u64);
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// What happened to a background task, sent as [`AppEvent::Task`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TaskEvent {
+    Started {
+        id: TaskId,
+        name: String,
+    },
+    /// `progress` is between `0.0` and `1.0`.
+    Progress {
+        id: TaskId,
+        progress: Option<f64>,
+        message: Option<String>,
+    },
+    /// The task finished, its result was sent as [`Event::Message`] right before.
+    Completed(TaskId),
+    Failed {
+        id: TaskId,
+        error: String,
+    },
+    Cancelled(TaskId),
+}
+
+impl TaskEvent {
+    pub fn id(&self) -> TaskId {
+        match self {
+            TaskEvent::Started { id, .. }
+            | TaskEvent::Progress { id, .. }
+            | TaskEvent::Failed { id, .. } => *id,
+            TaskEvent::Completed(id) | TaskEvent::Cancelled(id) => *id,
+        }
+    }
+}
+
+/// A task that is still running, see [`TaskManager::running`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunningTask {
+    pub id: TaskId,
+    pub name: String,
+    pub progress: Option<f64>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    task: RunningTask,
+    handle: AbortHandle,
+}
+
+/// The running tasks, shared by every [`TaskManager`] of one [`EventHandler`](crate::events::EventHandler).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Tasks {
+    next_id: Arc<AtomicU64>,
+    running: Arc<Mutex<BTreeMap<TaskId, Entry>>>,
+}
+
+impl Tasks {
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<TaskId, Entry>> {
+        // the map stays consistent even if a holder panicked
+        self.running
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Aborts every running task without sending events, used when the event handler stops.
+    pub(crate) fn abort_all(&self) {
+        for (_, entry) in std::mem::take(&mut *self.lock()) {
+            entry.handle.abort();
+        }
+    }
+}
+
+/// Handed to a task to report how far it got.
+///
+/// Every report updates the [task list](TaskManager::running) and sends a
+/// [`TaskEvent::Progress`].
+pub struct Progress<M = ()> {
+    id: TaskId,
+    tasks: Tasks,
+    sender: mpsc::UnboundedSender<Event<M>>,
+}
+
+impl<M> fmt::Debug for Progress<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<M> Progress<M> {
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Sets the progress, clamped to `0.0..=1.0`.
+    pub fn set(&self, progress: f64) {
+        self.report(Some(progress), None);
+    }
+
+    pub fn message(&self, message: &str) {
+        self.report(None, Some(String::from(message)));
+    }
+
+    /// Updates what is given, `None` keeps the previous value.
+    ///
+    /// The progress is clamped to `0.0..=1.0`, NaN counts as `None`.
+    pub fn report(&self, progress: Option<f64>, message: Option<String>) {
+        let progress = progress
+            .filter(|progress| !progress.is_nan())
+            .map(|progress| progress.clamp(0.0, 1.0));
+        if let Some(entry) = self.tasks.lock().get_mut(&self.id) {
+            entry.task.progress = progress.or(entry.task.progress);
+            entry.task.message = message.clone().or(entry.task.message.take());
+        }
+        let _ = self
+            .sender
+            .send(Event::App(AppEvent::Task(TaskEvent::Progress {
+                id: self.id,
+                progress,
+                message,
+            })));
+    }
+}
+
+/// Spawns futures in the background and reports on them through the event channel.
+///
+/// Get one from [`EventHandler::tasks`](crate::events::EventHandler::tasks) or
+/// [`AppEventSender::tasks`](crate::events::AppEventSender::tasks). All tasks are aborted when
+/// the event handler is stopped.
+pub struct TaskManager<M = ()> {
+    tasks: Tasks,
+    sender: mpsc::UnboundedSender<Event<M>>,
+}
+
+// derived Clone would require `M: Clone`
+impl<M> Clone for TaskManager<M> {
+    fn clone(&self) -> Self {
+        Self {
+            tasks: self.tasks.clone(),
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<M> fmt::Debug for TaskManager<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskManager")
+            .field("tasks", &self.tasks)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<M: Send + 'static> TaskManager<M> {
+    pub(crate) fn new(tasks: Tasks, sender: mpsc::UnboundedSender<Event<M>>) -> Self {
+        Self { tasks, sender }
+    }
+
+    /// Runs the future returned by `task` in the background.
+    ///
+    /// Sends [`TaskEvent::Started`] right away. If the task succeeds its result is sent as
+    /// [`Event::Message`] followed by [`TaskEvent::Completed`], if it fails or panics a
+    /// [`TaskEvent::Failed`] is sent. Nothing is sent once the task was cancelled.
+    pub fn spawn<F>(&self, name: &str, task: impl FnOnce(Progress<M>) -> F) -> TaskId
+    where
+        F: Future<Output = color_eyre::Result<M>> + Send + 'static,
+    {
+        let id = TaskId(self.tasks.next_id.fetch_add(1, Ordering::Relaxed));
+        let progress = Progress {
+            id,
+            tasks: self.tasks.clone(),
+            sender: self.sender.clone(),
+        };
+        let future = task(progress);
+        self.send(TaskEvent::Started {
+            id,
+            name: String::from(name),
+        });
+        let tasks = self.tasks.clone();
+        let sender = self.sender.clone();
+        // the lock is held until the task is registered, so it can not finish before that
+        let mut running = self.tasks.lock();
+        let handle = tokio::spawn(async move {
+            let result = AssertUnwindSafe(future).catch_unwind().await;
+            // a task cancelled while it finished already reported `Cancelled`
+            if tasks.lock().remove(&id).is_none() {
+                return;
+            }
+            let event = match result {
+                Ok(Ok(message)) => {
+                    let _ = sender.send(Event::Message(message));
+                    TaskEvent::Completed(id)
+                }
+                Ok(Err(error)) => TaskEvent::Failed {
+                    id,
+                    error: format!("{error:#}"),
+                },
+                Err(panic) => TaskEvent::Failed {
+                    id,
+                    error: panic_message(panic.as_ref()),
+                },
+            };
+            let _ = sender.send(Event::App(AppEvent::Task(event)));
+        });
+        let task = RunningTask {
+            id,
+            name: String::from(name),
+            progress: None,
+            message: None,
+        };
+        running.insert(
+            id,
+            Entry {
+                task,
+                handle: handle.abort_handle(),
+            },
+        );
+        id
+    }
+
+    /// Aborts the task and sends [`TaskEvent::Cancelled`], returns `false` if it already ended.
+    pub fn cancel(&self, id: TaskId) -> bool {
+        let Some(entry) = self.tasks.lock().remove(&id) else {
+            return false;
+        };
+        entry.handle.abort();
+        self.send(TaskEvent::Cancelled(id));
+        true
+    }
+
+    /// Cancels every running task.
+    pub fn cancel_all(&self) {
+        let ids: Vec<TaskId> = self.tasks.lock().keys().copied().collect();
+        for id in ids {
+            self.cancel(id);
+        }
+    }
+
+    pub fn is_running(&self, id: TaskId) -> bool {
+        self.tasks.lock().contains_key(&id)
+    }
+
+    /// The running tasks, oldest first.
+    pub fn running(&self) -> Vec<RunningTask> {
+        self.tasks
+            .lock()
+            .values()
+            .map(|entry| entry.task.clone())
+            .collect()
+    }
+
+    fn send(&self, event: TaskEvent) {
+        // Ignores the result because shutting down the app drops the receiver, which causes the send
+        // operation to fail. This is expected behavior and should not panic.
+        let _ = self.sender.send(Event::App(AppEvent::Task(event)));
+    }
+}
+
+/// The message of a panic payload, panics usually carry a `&str` or a `String`.
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause");
+    format!("the task panicked: {message}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::source::ScriptedSource;
+    use crate::events::test_utils::dummy_event_sender;
+    use crate::events::{AppEventSender, EventHandler};
+    use color_eyre::eyre::eyre;
+
+    fn task_event<M>(event: Event<M>) -> TaskEvent {
+        match event {
+            Event::App(AppEvent::Task(event)) => event,
+            _ => panic!("not a task event"),
+        }
+    }
+
+    #[tokio::test]
+    async fn tasks_report_and_cancel() {
+        let (sender, mut receiver) = dummy_event_sender::<u32>();
+        let tasks = AppEventSender::from(sender).tasks();
+
+        let (go, wait) = tokio::sync::oneshot::channel::<()>();
+        let id = tasks.spawn("load", |progress| async move {
+            progress.set(0.5);
+            progress.report(Some(f64::NAN), None);
+            progress.report(Some(1.5), None);
+            wait.await?;
+            Ok(42)
+        });
+        assert!(
+            matches!(task_event(receiver.next().await.unwrap()), TaskEvent::Started { name, .. } if name == "load")
+        );
+        assert!(matches!(
+            task_event(receiver.next().await.unwrap()),
+            TaskEvent::Progress { progress: Some(p), .. } if p == 0.5
+        ));
+        assert!(matches!(
+            task_event(receiver.next().await.unwrap()),
+            TaskEvent::Progress { progress: None, .. }
+        ));
+        assert!(matches!(
+            task_event(receiver.next().await.unwrap()),
+            TaskEvent::Progress { progress: Some(p), .. } if p == 1.0
+        ));
+        assert_eq!(tasks.running()[0].progress, Some(1.0));
+        go.send(()).unwrap();
+        assert!(matches!(receiver.next().await.unwrap(), Event::Message(42)));
+        assert_eq!(
+            task_event(receiver.next().await.unwrap()),
+            TaskEvent::Completed(id)
+        );
+        assert!(!tasks.is_running(id));
+
+        let failed = tasks.spawn("fail", |_| async { Err(eyre!("no network")) });
+        receiver.next().await.unwrap();
+        assert_eq!(
+            task_event(receiver.next().await.unwrap()),
+            TaskEvent::Failed {
+                id: failed,
+                error: String::from("no network")
+            }
+        );
+
+        let stuck = tasks.spawn("stuck", |_| std::future::pending());
+        receiver.next().await.unwrap();
+        assert!(tasks.cancel(stuck));
+        assert!(!tasks.cancel(stuck));
+        assert_eq!(
+            task_event(receiver.next().await.unwrap()),
+            TaskEvent::Cancelled(stuck)
+        );
+
+        // a task cancelled while it finishes reports nothing after `Cancelled`
+        let manager = tasks.clone();
+        let raced = tasks.spawn("race", move |progress| async move {
+            manager.cancel(progress.id());
+            Ok(7)
+        });
+        receiver.next().await.unwrap();
+        assert_eq!(
+            task_event(receiver.next().await.unwrap()),
+            TaskEvent::Cancelled(raced)
+        );
+
+        let panicked = tasks.spawn("boom", |_| async {
+            let parts: Vec<u32> = Vec::new();
+            Ok(parts[0])
+        });
+        receiver.next().await.unwrap();
+        assert!(matches!(
+            task_event(receiver.next().await.unwrap()),
+            TaskEvent::Failed { id, error } if id == panicked && error.starts_with("the task panicked")
+        ));
+        assert!(tasks.running().is_empty());
+
+        let events: EventHandler = EventHandler::builder()
+            .source(ScriptedSource::new())
+            .build();
+        let tasks = events.tasks();
+        let id = tasks.spawn("stuck", |_| std::future::pending());
+        assert!(tasks.is_running(id));
+        events.stop();
+        assert!(tasks.running().is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::events::Event;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+use tokio::time::{self, Instant, MissedTickBehavior};
+
+/// Shortest period of [`send_every`], a zero period would make the interval panic.
+const MIN_PERIOD: Duration = Duration::from_millis(1);
+
+/// Handle to an event scheduled with [`EventSender::send_after`](crate::events::EventSender::send_after)
+/// and friends.
+///
+/// Dropping the handle does not cancel the timer, call [`TimerHandle::cancel`] for that.
+#[derive(Debug, Clone)]
+pub struct TimerHandle {
+    handle: AbortHandle,
+}
+
+impl TimerHandle {
+    /// Stops the timer, a pending event is not sent.
+    pub fn cancel(&self) {
+        self.handle.abort();
+    }
+
+    /// Whether the timer fired for the last time or was cancelled.
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+}
+
+/// Sends `event` once `deadline` is reached.
+pub(crate) fn send_at<M: Send + 'static>(
+    sender: &mpsc::UnboundedSender<Event<M>>,
+    deadline: Instant,
+    event: Event<M>,
+) -> TimerHandle {
+    let sender = sender.clone();
+    let handle = tokio::spawn(async move {
+        time::sleep_until(deadline).await;
+        // Ignores the result because shutting down the app drops the receiver, which causes the send
+        // operation to fail. This is expected behavior and should not panic.
+        let _ = sender.send(event);
+    });
+    TimerHandle {
+        handle: handle.abort_handle(),
+    }
+}
+
+/// Sends `event` after `delay`.
+pub(crate) fn send_after<M: Send + 'static>(
+    sender: &mpsc::UnboundedSender<Event<M>>,
+    delay: Duration,
+    event: Event<M>,
+) -> TimerHandle {
+    send_at(sender, Instant::now() + delay, event)
+}
+
+/// Sends `event` every `period`, starting one period from now.
+///
+/// Periods shorter than [`MIN_PERIOD`] are raised to it. The timer stops by itself once the
+/// receiver is gone.
+pub(crate) fn send_every<M: Clone + Send + 'static>(
+    sender: &mpsc::UnboundedSender<Event<M>>,
+    period: Duration,
+    event: Event<M>,
+) -> TimerHandle {
+    let sender = sender.clone();
+    let period = period.max(MIN_PERIOD);
+    let handle = tokio::spawn(async move {
+        let mut interval = time::interval_at(Instant::now() + period, period);
+        // a busy app should not get a burst of stale events
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if sender.send(event.clone()).is_err() {
+                break;
+            }
+        }
+    });
+    TimerHandle {
+        handle: handle.abort_handle(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::events::{AppEvent, AppEventSender, Event, test_utils::dummy_event_sender};
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn timers_fire_and_cancel() {
+        let (sender, mut receiver) = dummy_event_sender::<u32>();
+        let app_sender: AppEventSender<u32> = sender.clone().into();
+
+        let cancelled = app_sender.send_message_after(Duration::from_millis(50), 0);
+        app_sender.send_message_after(Duration::from_millis(100), 1);
+        let interval = sender.send_every(Duration::from_millis(30), AppEvent::Clear(false).into());
+        cancelled.cancel();
+
+        for _ in 0..3 {
+            assert!(matches!(
+                receiver.next().await.unwrap(),
+                Event::App(AppEvent::Clear(false))
+            ));
+        }
+        assert!(matches!(receiver.next().await.unwrap(), Event::Message(1)));
+        interval.cancel();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(interval.is_finished());
+
+        // a zero period fires every millisecond instead of panicking
+        let zero = app_sender.send_message_every(Duration::ZERO, 2);
+        let start = tokio::time::Instant::now();
+        for _ in 0..3 {
+            assert!(matches!(receiver.next().await.unwrap(), Event::Message(2)));
+        }
+        assert_eq!(start.elapsed(), Duration::from_millis(3));
+        assert!(!zero.is_finished());
+        zero.cancel();
+    }
+}
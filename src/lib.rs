@@ -1,4 +1,9 @@
+pub mod app;
+pub mod clipboard;
 pub mod componets;
+pub mod elm;
 pub mod events;
+pub mod persistence;
+pub mod prompt;
 pub mod utils;
 pub mod widgets;
@@ -0,0 +1,157 @@
+use crate::componets::component::Component;
+use color_eyre::eyre::{OptionExt, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the file the state is stored in, see [`StateStore::default_path`].
+const STATE_FILE: &str = "state.json";
+
+/// Snapshot of the state of every widget in a [`Component`] tree.
+///
+/// Widgets are keyed by their [`Widget::id`](crate::widgets::Widget::id), so the
+/// restored values end up in the right widget even if the tree was reordered in
+/// between. Widgets without an id are not persisted.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateStore {
+    /// Id of the widget that had focus.
+    focus: Option<String>,
+    widgets: BTreeMap<String, serde_json::Value>,
+}
+
+impl StateStore {
+    /// Collects the state of all widgets in `root`.
+    pub fn capture(root: &Component) -> Self {
+        let mut store = Self::default();
+        for widget in root.iter() {
+            let widget = widget.borrow();
+            let Some(id) = widget.id() else {
+                continue;
+            };
+            if widget.is_focused() {
+                store.focus = Some(id.to_string());
+            }
+            if let Some(state) = widget.save_state() {
+                store.widgets.insert(id.to_string(), state);
+            }
+        }
+        store
+    }
+
+    /// Writes the stored state back into the widgets of `root`.
+    ///
+    /// Widgets that are not part of the store are left untouched, values of widgets that no
+    /// longer exist are ignored.
+    pub fn apply(&self, root: &Component) {
+        for widget in root.iter() {
+            let mut widget = widget.borrow_mut();
+            let Some(id) = widget.id().map(String::from) else {
+                continue;
+            };
+            if let Some(state) = self.widgets.get(&id) {
+                widget.restore_state(state.clone());
+            }
+            if let Some(focus) = self.focus.as_ref() {
+                if *focus == id {
+                    widget.focus();
+                } else if widget.is_focused() {
+                    widget.unfocus();
+                }
+            }
+        }
+    }
+
+    pub fn focus(&self) -> Option<&str> {
+        self.focus.as_deref()
+    }
+
+    /// Reads a store from `path`.
+    ///
+    /// A missing file is not an error and yields an empty store, so the first launch of an
+    /// application does not need special handling.
+    pub fn load(path: impl AsRef<Path>) -> color_eyre::Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read state from {}", path.display()))?;
+        serde_json::from_str(&content)
+            .wrap_err_with(|| format!("failed to parse state in {}", path.display()))
+    }
+
+    /// Writes the store to `path`, creating missing parent directories.
+    pub fn save(&self, path: impl AsRef<Path>) -> color_eyre::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)
+            .wrap_err_with(|| format!("failed to write state to {}", path.display()))
+    }
+
+    /// Location of the state file of the application `app_name` in the users data directory.
+    pub fn default_path(app_name: &str) -> color_eyre::Result<PathBuf> {
+        let dir = dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .ok_or_eyre("Failed to find a directory for the application state")?;
+        Ok(dir.join(app_name).join(STATE_FILE))
+    }
+}
+
+/// Saves the state of all widgets in `root` to `path`, call this before quitting.
+pub fn save(root: &Component, path: impl AsRef<Path>) -> color_eyre::Result<()> {
+    StateStore::capture(root).save(path)
+}
+
+/// Restores the state saved by [`save`] into `root`, call this after building the widgets.
+pub fn restore(root: &Component, path: impl AsRef<Path>) -> color_eyre::Result<()> {
+    StateStore::load(path)?.apply(root);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widgets::{Button, InputWidget, Widget};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::rc::Rc;
+
+    fn type_text(input: &mut InputWidget, text: &str) {
+        for c in text.chars() {
+            input.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn restores_by_id() {
+        let mut name = InputWidget::new("Name").with_id("name");
+        let mut mail = InputWidget::new("Mail").with_id("mail");
+        let mut ok = Button::new("Ok", 'o').with_id("ok");
+        type_text(&mut name, "alice");
+        type_text(&mut mail, "a@b.c");
+        ok.focus();
+        let root = Component::ListView(Rc::from([name.into(), mail.into(), ok.into()]));
+
+        let json = serde_json::to_string(&StateStore::capture(&root)).unwrap();
+        let store: StateStore = serde_json::from_str(&json).unwrap();
+        assert_eq!(store.focus(), Some("ok"));
+
+        // same widgets in a different order
+        let name = InputWidget::new("Name").with_id("name").boxed();
+        let mail = InputWidget::new("Mail").with_id("mail").boxed();
+        let ok = Button::new("Ok", 'o').with_id("ok").boxed();
+        let root = Component::ListView(Rc::from([
+            Component::Widget(ok.clone()),
+            Component::Widget(mail.clone()),
+            Component::Widget(name.clone()),
+        ]));
+        store.apply(&root);
+        assert_eq!(name.borrow().get_content(), "alice");
+        assert_eq!(mail.borrow().get_content(), "a@b.c");
+        assert!(ok.borrow().is_focused());
+        assert!(!name.borrow().is_focused());
+    }
+}
@@ -0,0 +1,659 @@
+use crate::app::{App, Runner};
+use crate::componets::component::Component;
+use crate::events::keymap::{GLOBAL_MODE, Keymap};
+use crate::events::{AppEvent, AppEventSender, Event, EventHandler};
+use crate::widgets::{Button, InputWidget, Widget, WidgetEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, BorderType, Widget as UiWidget};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// Action the prompts bind to Esc.
+const CANCEL_ACTION: &str = "cancel";
+/// Most options a [`SelectPrompt`] shows at once.
+const MAX_OPTIONS: usize = 10;
+
+type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+type Submit<T> = Box<dyn FnMut(WidgetEvent) -> Option<Result<T, String>>>;
+type Content<T> = Box<dyn FnOnce(&AppEventSender<T>) -> Component>;
+
+/// The user cancelled a prompt with Esc or Ctrl+C.
+///
+/// Returned inside the [`Report`](color_eyre::Report), check for it with
+/// `error.downcast_ref::<Cancelled>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the prompt was cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Asks for a line of text below the cursor.
+pub async fn prompt_text(label: &str) -> color_eyre::Result<String> {
+    TextPrompt::new(label).run().await
+}
+
+/// Asks for a line of text without showing it.
+pub async fn prompt_password(label: &str) -> color_eyre::Result<String> {
+    TextPrompt::new(label).password().run().await
+}
+
+/// Asks a yes/no question, `y` and `n` answer it right away.
+pub async fn confirm(question: &str) -> color_eyre::Result<bool> {
+    ConfirmPrompt::new(question).run().await
+}
+
+/// Asks to pick one of `options`, returns its index.
+pub async fn select(label: &str, options: &[impl AsRef<str>]) -> color_eyre::Result<usize> {
+    SelectPrompt::new(label, options).run().await
+}
+
+/// A prompt for a line of text, see [`prompt_text`].
+///
+/// Enter submits the text, if the validator rejects it the error is shown below the input and
+/// the prompt stays open.
+pub struct TextPrompt {
+    label: String,
+    password: bool,
+    validate: Option<Validator>,
+    fullscreen: bool,
+}
+
+impl TextPrompt {
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: String::from(label),
+            password: false,
+            validate: None,
+            fullscreen: false,
+        }
+    }
+
+    pub fn password(mut self) -> Self {
+        self.password = true;
+        self
+    }
+
+    /// Accepts the text only if `validate` returns `Ok`, the error is shown to the user.
+    pub fn validate(mut self, validate: impl Fn(&str) -> Result<(), String> + 'static) -> Self {
+        self.validate = Some(Box::new(validate));
+        self
+    }
+
+    /// Uses the alternate screen instead of drawing below the cursor.
+    pub fn fullscreen(mut self) -> Self {
+        self.fullscreen = true;
+        self
+    }
+
+    pub async fn run(self) -> color_eyre::Result<String> {
+        let fullscreen = self.fullscreen;
+        ask(self.form(), fullscreen).await
+    }
+
+    fn form(self) -> Form<String> {
+        let mut input = InputWidget::new(&self.label);
+        if self.password {
+            input = input.password();
+        }
+        let input = input.boxed();
+        input.borrow_mut().focus();
+        let validate = self.validate;
+        let submit = move |event| match event {
+            WidgetEvent::Input(text) => Some(match validate.as_ref() {
+                Some(validate) => validate(&text).map(|()| text),
+                None => Ok(text),
+            }),
+            _ => None,
+        };
+        let content = Component::Widget(input).sized(Constraint::Length(3));
+        Form::new(move |_| content, 3, submit)
+    }
+}
+
+/// A yes/no question, see [`confirm`].
+pub struct ConfirmPrompt {
+    question: String,
+    fullscreen: bool,
+}
+
+impl ConfirmPrompt {
+    pub fn new(question: &str) -> Self {
+        Self {
+            question: String::from(question),
+            fullscreen: false,
+        }
+    }
+
+    pub fn fullscreen(mut self) -> Self {
+        self.fullscreen = true;
+        self
+    }
+
+    pub async fn run(self) -> color_eyre::Result<bool> {
+        let fullscreen = self.fullscreen;
+        ask(self.form(), fullscreen).await
+    }
+
+    fn form(self) -> Form<bool> {
+        let question = self.question;
+        // each button sends its answer as the message of the form
+        let content = move |sender: &AppEventSender<bool>| {
+            let yes = Button::new("Yes", 'y').on_press(sender, true).boxed();
+            let no = Button::new("No", 'n').on_press(sender, false).boxed();
+            yes.borrow_mut().focus();
+            Component::ListView(Rc::from([
+                Component::from(Label::new(&question, Style::new())).sized(Constraint::Length(1)),
+                Component::GridView(Rc::from([Rc::from([
+                    Component::Widget(yes).sized(Constraint::Length(9)),
+                    Component::Widget(no).sized(Constraint::Length(9)),
+                ])]))
+                .sized(Constraint::Length(3)),
+            ]))
+        };
+        Form::new(content, 4, |_| None)
+            .shortcut("y", true)
+            .shortcut("n", false)
+    }
+}
+
+/// A list of options, see [`select`].
+///
+/// Up and Down (or `k` and `j`) move the selection, Enter or a click picks an option.
+pub struct SelectPrompt {
+    label: String,
+    options: Vec<String>,
+    fullscreen: bool,
+}
+
+impl SelectPrompt {
+    pub fn new(label: &str, options: &[impl AsRef<str>]) -> Self {
+        Self {
+            label: String::from(label),
+            options: options.iter().map(|o| String::from(o.as_ref())).collect(),
+            fullscreen: false,
+        }
+    }
+
+    pub fn fullscreen(mut self) -> Self {
+        self.fullscreen = true;
+        self
+    }
+
+    pub async fn run(self) -> color_eyre::Result<usize> {
+        let fullscreen = self.fullscreen;
+        ask(self.form(), fullscreen).await
+    }
+
+    fn form(self) -> Form<usize> {
+        let height = self.options.len().min(MAX_OPTIONS) as u16 + 2;
+        let choices = Rc::new(RefCell::new(Choices {
+            title: self.label,
+            options: self.options,
+            selected: 0,
+            focused: true,
+        }));
+        let submit = {
+            let choices = choices.clone();
+            move |event| match event {
+                WidgetEvent::Button => Some(Ok(choices.borrow().selected)),
+                _ => None,
+            }
+        };
+        let content = Component::Widget(choices).sized(Constraint::Length(height));
+        Form::new(move |_| content, height, submit)
+    }
+}
+
+/// Runs `form` until it is answered or cancelled, the terminal is restored in both cases.
+async fn ask<T: Clone + Send + 'static>(
+    mut form: Form<T>,
+    fullscreen: bool,
+) -> color_eyre::Result<T> {
+    let mut runner = Runner::new()
+        .events(EventHandler::builder().keymap(form.keymap()?))
+        .mouse_capture(fullscreen);
+    if !fullscreen {
+        // one more line for the validation error
+        runner = runner.inline(form.height + 1);
+    }
+    runner.run(&mut form).await?;
+    form.into_answer()
+}
+
+/// The app behind every prompt: a tree, a way to turn widget events into the answer and an
+/// error line.
+///
+/// The tree is built in [`App::init`] so its widgets can send the answer as the message of the
+/// form.
+struct Form<T> {
+    content: Option<Content<T>>,
+    root: Component,
+    height: u16,
+    submit: Submit<T>,
+    /// Keys that answer the prompt right away, the keys are also the name of their action.
+    shortcuts: Vec<(&'static str, T)>,
+    error: Rc<RefCell<Label>>,
+    answer: Option<T>,
+    sender: Option<AppEventSender<T>>,
+}
+
+impl<T: Clone> Form<T> {
+    fn new(
+        content: impl FnOnce(&AppEventSender<T>) -> Component + 'static,
+        height: u16,
+        submit: impl FnMut(WidgetEvent) -> Option<Result<T, String>> + 'static,
+    ) -> Self {
+        let error = Rc::new(RefCell::new(Label::new("", Style::new().fg(Color::Red))));
+        let root = Component::ListView(Rc::from([Component::Widget(error.clone())]));
+        Self {
+            content: Some(Box::new(content)),
+            root,
+            height,
+            submit: Box::new(submit),
+            shortcuts: Vec::new(),
+            error,
+            answer: None,
+            sender: None,
+        }
+    }
+
+    fn shortcut(mut self, keys: &'static str, answer: T) -> Self {
+        self.shortcuts.push((keys, answer));
+        self
+    }
+
+    /// Esc cancels the prompt, the shortcuts answer it.
+    fn keymap(&self) -> color_eyre::Result<Keymap> {
+        let mut keymap = Keymap::default();
+        keymap.bind(GLOBAL_MODE, "esc", CANCEL_ACTION)?;
+        for (keys, _) in &self.shortcuts {
+            keymap.bind(GLOBAL_MODE, keys, keys)?;
+        }
+        Ok(keymap)
+    }
+
+    /// The answer, or [`Cancelled`] if the prompt quit without one.
+    fn into_answer(self) -> color_eyre::Result<T> {
+        self.answer.ok_or_else(|| Cancelled.into())
+    }
+
+    fn finish(&mut self, answer: Option<T>) {
+        self.answer = answer;
+        if let Some(sender) = self.sender.as_ref() {
+            sender.send(AppEvent::Quit);
+        }
+    }
+}
+
+impl<T: Clone + Send + 'static> App for Form<T> {
+    type Message = T;
+
+    fn init(&mut self, sender: AppEventSender<T>) -> color_eyre::Result<()> {
+        if let Some(content) = self.content.take() {
+            self.root = Component::ListView(Rc::from([
+                content(&sender),
+                Component::Widget(self.error.clone()).sized(Constraint::Length(1)),
+            ]));
+        }
+        self.sender = Some(sender);
+        Ok(())
+    }
+
+    fn root(&self) -> Component {
+        self.root.clone()
+    }
+
+    fn handle_event(&mut self, event: Event<T>) -> color_eyre::Result<()> {
+        match event {
+            Event::App(AppEvent::Action(action)) if action == CANCEL_ACTION => self.finish(None),
+            Event::App(AppEvent::Action(action)) => {
+                if let Some((_, answer)) = self.shortcuts.iter().find(|(k, _)| *k == action) {
+                    self.finish(Some(answer.clone()));
+                }
+            }
+            Event::Message(answer) => self.finish(Some(answer)),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_widget_event(&mut self, event: WidgetEvent) -> color_eyre::Result<()> {
+        match (self.submit)(event) {
+            Some(Ok(answer)) => self.finish(Some(answer)),
+            Some(Err(error)) => self.error.borrow_mut().text = error,
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+/// A line of text that can not be focused.
+#[derive(Debug)]
+struct Label {
+    text: String,
+    style: Style,
+}
+
+impl Label {
+    fn new(text: &str, style: Style) -> Self {
+        Self {
+            text: String::from(text),
+            style,
+        }
+    }
+}
+
+impl Widget for Label {
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    fn clear(&mut self, _: bool) {
+        self.text.clear();
+    }
+
+    fn draw(&self, area: Rect, buf: &mut Buffer, _: &mut Option<u16>) {
+        buf.set_stringn(area.x, area.y, &self.text, area.width as usize, self.style);
+    }
+}
+
+/// The options of a [`SelectPrompt`].
+#[derive(Debug)]
+struct Choices {
+    title: String,
+    options: Vec<String>,
+    selected: usize,
+    focused: bool,
+}
+
+impl Choices {
+    /// Index of the first option shown in `area`, so the selected one is visible.
+    fn scroll(&self, area: Rect) -> usize {
+        let rows = area.height.saturating_sub(2).max(1) as usize;
+        (self.selected + 1).saturating_sub(rows)
+    }
+}
+
+impl Widget for Choices {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn clear(&mut self, _: bool) {
+        self.selected = 0;
+    }
+
+    fn wants_key(&self, key: KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Up | KeyCode::Down)
+    }
+
+    fn handle_key_event(&mut self, event: KeyEvent) -> Option<WidgetEvent> {
+        if !event.is_press() {
+            return None;
+        }
+        let last = self.options.len().saturating_sub(1);
+        match event.code {
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            KeyCode::Enter if !self.options.is_empty() => return Some(WidgetEvent::Button),
+            _ => {}
+        }
+        None
+    }
+
+    /// Picks the clicked option.
+    fn handle_mouse_event(&mut self, event: MouseEvent, area: Rect) -> Option<WidgetEvent> {
+        if event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return None;
+        }
+        // the options start below the top border
+        let row = event.row.checked_sub(area.y + 1)? as usize + self.scroll(area);
+        if row >= self.options.len() {
+            return None;
+        }
+        self.selected = row;
+        Some(WidgetEvent::Button)
+    }
+
+    fn draw(&self, area: Rect, buf: &mut Buffer, _: &mut Option<u16>) {
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(self.title.as_str());
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let options = self.options.iter().enumerate().skip(self.scroll(area));
+        for (row, (index, option)) in (inner.y..inner.bottom()).zip(options) {
+            let (marker, style) = if index == self.selected {
+                ("> ", Style::new().add_modifier(Modifier::REVERSED))
+            } else {
+                ("  ", Style::new())
+            };
+            buf.set_stringn(
+                inner.x,
+                row,
+                format!("{marker}{option}"),
+                inner.width as usize,
+                style,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::componets::focus::{FocusDirection, FocusManager};
+    use crate::events::source::{ManualClock, ScriptedSource};
+    use crate::events::test_utils::dummy_event_sender;
+    use crossterm::event::{KeyEventKind, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    /// Presses and releases Enter on the focused widget of `form`.
+    fn enter<T: Clone + Send + 'static>(form: &mut Form<T>) {
+        let root = form.root();
+        let widget = root.focused().unwrap().clone();
+        let event = widget.borrow_mut().handle_key_event(key(KeyCode::Enter));
+        let mut release = key(KeyCode::Enter);
+        release.kind = KeyEventKind::Release;
+        widget.borrow_mut().handle_key_event(release);
+        form.handle_widget_event(event.unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn validation_and_cancel() {
+        let mut form = TextPrompt::new("port")
+            .validate(|text| {
+                text.parse::<u16>()
+                    .map(|_| ())
+                    .map_err(|_| String::from("not a port"))
+            })
+            .form();
+        let (sender, mut receiver) = dummy_event_sender();
+        form.init(sender.into()).unwrap();
+
+        form.handle_widget_event(WidgetEvent::Input("http".into()))
+            .unwrap();
+        assert_eq!(form.error.borrow().text, "not a port");
+        assert_eq!(form.answer, None);
+        form.handle_widget_event(WidgetEvent::Input("8080".into()))
+            .unwrap();
+        assert_eq!(form.answer.as_deref(), Some("8080"));
+        assert!(matches!(
+            receiver.next().await.unwrap(),
+            Event::App(AppEvent::Quit)
+        ));
+
+        form.handle_event(Event::App(AppEvent::Action(CANCEL_ACTION.into())))
+            .unwrap();
+        assert_eq!(form.answer, None);
+        let error: color_eyre::Report = Cancelled.into();
+        assert!(error.downcast_ref::<Cancelled>().is_some());
+    }
+
+    #[tokio::test]
+    async fn confirm_answers() {
+        let mut form = ConfirmPrompt::new("delete?").form();
+        let (sender, mut receiver) = dummy_event_sender();
+        form.init(sender.into()).unwrap();
+
+        // each button sends its own answer, whatever state the other one is in
+        enter(&mut form);
+        let answer = receiver.next().await.unwrap();
+        assert!(matches!(answer, Event::Message(true)));
+        form.handle_event(answer).unwrap();
+        assert_eq!(form.answer, Some(true));
+        assert!(matches!(
+            receiver.next().await.unwrap(),
+            Event::App(AppEvent::Quit)
+        ));
+        FocusManager::new().move_focus(&form.root(), FocusDirection::Right);
+        enter(&mut form);
+        let answer = receiver.next().await.unwrap();
+        assert!(matches!(answer, Event::Message(false)));
+        form.handle_event(answer).unwrap();
+        assert_eq!(form.answer, Some(false));
+        drop(receiver.next().await.unwrap());
+
+        form.handle_event(Event::App(AppEvent::Action("y".into())))
+            .unwrap();
+        assert_eq!(form.answer, Some(true));
+        form.handle_event(Event::App(AppEvent::Action("n".into())))
+            .unwrap();
+        assert_eq!(form.answer, Some(false));
+    }
+
+    #[tokio::test]
+    async fn keys_answer_or_cancel() {
+        let form = ConfirmPrompt::new("delete?").form();
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let mut events: EventHandler<bool> = EventHandler::builder()
+            .keymap(form.keymap().unwrap())
+            .manual_clock(ManualClock::new())
+            .source(
+                ScriptedSource::new()
+                    .key(KeyCode::Char('n'))
+                    .key(KeyCode::Esc)
+                    .key_event(ctrl_c),
+            )
+            .build();
+        let (sender, _receiver) = dummy_event_sender();
+        let mut forms = [form, ConfirmPrompt::new("delete?").form()];
+        for form in &mut forms {
+            form.init(sender.clone().into()).unwrap();
+        }
+
+        forms[0].handle_event(events.next().await.unwrap()).unwrap();
+        assert_eq!(forms[0].answer, Some(false));
+        forms[0].handle_event(events.next().await.unwrap()).unwrap();
+        assert_eq!(forms[0].answer, None);
+        // Ctrl+C quits the runner without an answer
+        let quit = events.next().await.unwrap();
+        assert!(matches!(quit, Event::App(AppEvent::Quit)));
+        forms[1].handle_event(quit).unwrap();
+        events.stop();
+        let [_, form] = forms;
+        let error = form.into_answer().unwrap_err();
+        assert!(error.downcast_ref::<Cancelled>().is_some());
+    }
+
+    #[test]
+    fn choices_move_and_scroll() {
+        let options: Vec<_> = ('a'..='l').map(String::from).collect();
+        let mut choices = Choices {
+            title: String::from("pick"),
+            options,
+            selected: 0,
+            focused: true,
+        };
+        // ten options fit between the borders
+        let area = Rect::new(0, 5, 20, 12);
+        let mut press = |code| {
+            let event = choices.handle_key_event(key(code));
+            (event, choices.selected, choices.scroll(area))
+        };
+        assert_eq!(press(KeyCode::Char('j')), (None, 1, 0));
+        assert_eq!(press(KeyCode::Down), (None, 2, 0));
+        assert_eq!(press(KeyCode::Char('k')), (None, 1, 0));
+        assert_eq!(press(KeyCode::End), (None, 11, 2));
+        assert_eq!(press(KeyCode::Down), (None, 11, 2));
+        assert_eq!(press(KeyCode::Home), (None, 0, 0));
+        assert_eq!(press(KeyCode::Up), (None, 0, 0));
+        assert_eq!(press(KeyCode::Enter), (Some(WidgetEvent::Button), 0, 0));
+
+        let mut buf = Buffer::empty(area);
+        choices.selected = 11;
+        choices.draw(area, &mut buf, &mut None);
+        assert_eq!(buf[(3, 6)].symbol(), "c");
+        assert_eq!(buf[(3, 15)].symbol(), "l");
+
+        let click = |row| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 3,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        // the border is not an option
+        assert_eq!(choices.handle_mouse_event(click(5), area), None);
+        assert_eq!(
+            choices.handle_mouse_event(click(6), area),
+            Some(WidgetEvent::Button)
+        );
+        assert_eq!(choices.selected, 2);
+
+        choices.options.clear();
+        choices.selected = 0;
+        assert_eq!(choices.handle_key_event(key(KeyCode::Enter)), None);
+        assert_eq!(choices.handle_mouse_event(click(6), area), None);
+    }
+
+    #[tokio::test]
+    async fn select_answers() {
+        let mut form = SelectPrompt::new("pick", &["red", "green", "blue"]).form();
+        let (sender, mut receiver) = dummy_event_sender();
+        form.init(sender.into()).unwrap();
+
+        let root = form.root();
+        let choices = root.focused().unwrap().clone();
+        choices
+            .borrow_mut()
+            .handle_key_event(key(KeyCode::Char('j')));
+        enter(&mut form);
+        assert_eq!(form.answer, Some(1));
+        assert!(matches!(
+            receiver.next().await.unwrap(),
+            Event::App(AppEvent::Quit)
+        ));
+
+        let mut form = SelectPrompt::new("pick", &[] as &[&str]).form();
+        let (sender, _receiver) = dummy_event_sender();
+        form.init(sender.into()).unwrap();
+        let root = form.root();
+        let event = root
+            .focused()
+            .unwrap()
+            .borrow_mut()
+            .handle_key_event(key(KeyCode::Enter));
+        assert_eq!(event, None);
+        assert_eq!(form.answer, None);
+    }
+}
@@ -1,5 +1,6 @@
-use crate::widgets::{self, Widget};
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use crate::events::AppEventSender;
+use crate::widgets::{self, Emitter, Widget};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -7,6 +8,7 @@ use ratatui::{
     text::Line,
     widgets::{Block, Widget as w},
 };
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Theme {
@@ -37,7 +39,7 @@ pub const GREEN: Theme = Theme {
     shadow: Color::Rgb(32, 96, 32),
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ButtonState {
     Normal,
     Selected,
@@ -46,25 +48,45 @@ pub enum ButtonState {
 
 #[derive(Debug, Clone)]
 pub struct Button {
+    id: Option<String>,
     state: ButtonState,
-    prev_state: ButtonState,
+    /// Kept apart from `state` so a pressed button keeps the focus.
+    focused: bool,
     label: String,
     theme: Theme,
     key_id: char,
-    on_press_id: String,
+    on_press: Option<Emitter>,
 }
 
 impl Button {
-    pub fn new(label: &str, key_id: char, on_press: &str) -> Self {
+    pub fn new(label: &str, key_id: char) -> Self {
         Self {
+            id: None,
             state: ButtonState::Normal,
-            prev_state: ButtonState::Normal,
+            focused: false,
             label: String::from(label),
             theme: BLUE,
             key_id,
-            on_press_id: on_press.to_uppercase().to_string(),
+            on_press: None,
         }
     }
+    /// Sends `message` through `sender` whenever the button is pressed.
+    pub fn on_press<M: Clone + 'static>(self, sender: &AppEventSender<M>, message: M) -> Self {
+        self.on_press_with(sender, move || message.clone())
+    }
+    /// Sends the message built by `message` through `sender` whenever the button is pressed.
+    pub fn on_press_with<M: 'static>(
+        mut self,
+        sender: &AppEventSender<M>,
+        message: impl Fn() -> M + 'static,
+    ) -> Self {
+        self.on_press = Some(Emitter::new(sender, move |()| message()));
+        self
+    }
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(String::from(id));
+        self
+    }
     pub fn is_pressed(&self) -> bool {
         self.state == ButtonState::Active
     }
@@ -81,33 +103,55 @@ impl Button {
         }
     }
 
+    /// The state the button returns to once it is released.
+    const fn resting(&self) -> ButtonState {
+        if self.focused {
+            ButtonState::Selected
+        } else {
+            ButtonState::Normal
+        }
+    }
+
     fn handle_press(&mut self, event: KeyEvent) -> Option<widgets::WidgetEvent> {
         match event.kind {
             KeyEventKind::Press => {
-                if self.state == ButtonState::Selected
-                    || event.code == KeyCode::Char(self.key_id.clone())
-                {
-                    self.prev_state = self.state;
-                    self.state = ButtonState::Active;
-                    return Some(widgets::WidgetEvent::Button(self.on_press_id.clone()));
+                // most terminals never report the release of a key
+                self.release();
+                if self.focused || event.code == KeyCode::Char(self.key_id) {
+                    return self.press();
                 }
                 None
             }
             KeyEventKind::Release => {
-                if self.state == ButtonState::Active {
-                    self.state = self.prev_state;
-                    return None;
-                }
+                self.release();
                 None
             }
             _ => None,
         }
     }
+
+    fn press(&mut self) -> Option<widgets::WidgetEvent> {
+        self.state = ButtonState::Active;
+        if let Some(on_press) = self.on_press.as_ref() {
+            on_press.emit(());
+        }
+        Some(widgets::WidgetEvent::Button)
+    }
+
+    fn release(&mut self) {
+        self.state = self.resting();
+    }
 }
 
 impl Widget for Button {
     fn clear(&mut self, _: bool) {
-        self.state = ButtonState::Normal;
+        self.release();
+    }
+
+    /// Releases a button that was pressed with a key, the terminal may never report the
+    /// release.
+    fn tick(&mut self) {
+        self.release();
     }
 
     fn handle_key_event(&mut self, event: KeyEvent) -> Option<widgets::WidgetEvent> {
@@ -119,11 +163,46 @@ impl Widget for Button {
         }
     }
 
+    fn handle_mouse_event(&mut self, event: MouseEvent, _: Rect) -> Option<widgets::WidgetEvent> {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.press(),
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.release();
+                None
+            }
+            _ => None,
+        }
+    }
+
     fn focus(&mut self) {
-        self.state = ButtonState::Selected;
+        self.focused = true;
+        if self.state != ButtonState::Active {
+            self.state = ButtonState::Selected;
+        }
     }
     fn unfocus(&mut self) {
-        self.state = ButtonState::Normal;
+        self.focused = false;
+        if self.state != ButtonState::Active {
+            self.state = ButtonState::Normal;
+        }
+    }
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn save_state(&self) -> Option<serde_json::Value> {
+        // a press is transient, persist the state the button returns to
+        serde_json::to_value(self.resting()).ok()
+    }
+    fn restore_state(&mut self, state: serde_json::Value) {
+        if let Ok(state) = serde_json::from_value::<ButtonState>(state) {
+            self.focused = state == ButtonState::Selected;
+            self.state = self.resting();
+        }
     }
 
     fn draw(&self, area: Rect, buf: &mut Buffer, _: &mut Option<u16>) {
@@ -146,3 +225,65 @@ impl Widget for Button {
         );
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::componets::component::{Component, WidgetType};
+    use crate::componets::focus::{FocusDirection, FocusManager};
+    use crate::events::{AppEventSender, Event, test_utils::dummy_event_sender};
+    use crossterm::event::KeyModifiers;
+    use std::rc::Rc;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Msg {
+        Save,
+    }
+
+    #[tokio::test]
+    async fn press_sends_message() {
+        let (sender, mut receiver) = dummy_event_sender::<Msg>();
+        let sender: AppEventSender<Msg> = sender.into();
+        let mut button = Button::new("Save", 's').on_press(&sender, Msg::Save);
+        let event = button.handle_key_event(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE));
+        assert_eq!(event, Some(widgets::WidgetEvent::Button));
+        assert!(button.is_pressed());
+        assert!(matches!(
+            receiver.next().await.unwrap(),
+            Event::Message(Msg::Save)
+        ));
+    }
+
+    #[test]
+    fn focus_survives_a_press() {
+        let button = Button::new("Clear", 'c').boxed();
+        let root = Component::ListView(Rc::from([
+            Component::Widget(button.clone()),
+            Component::from(Button::new("Quit", 'q')),
+        ]));
+        FocusManager::new().focus_index(&root, 0);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        // no release is reported, the focus stays while the button is drawn pressed
+        let event = button.borrow_mut().handle_key_event(enter);
+        assert_eq!(event, Some(widgets::WidgetEvent::Button));
+        assert!(button.borrow().is_pressed());
+        let widget: WidgetType = button.clone();
+        assert!(Rc::ptr_eq(root.focused().unwrap(), &widget));
+
+        button.borrow_mut().tick();
+        assert!(!button.borrow().is_pressed());
+        assert!(button.borrow().is_focused());
+
+        // a second press releases the first one and presses again
+        button.borrow_mut().handle_key_event(enter);
+        let event = button.borrow_mut().handle_key_event(enter);
+        assert_eq!(event, Some(widgets::WidgetEvent::Button));
+        assert_eq!(button.borrow().save_state(), Some("Selected".into()));
+
+        FocusManager::new().move_focus(&root, FocusDirection::Next);
+        assert!(!button.borrow().is_focused());
+        button.borrow_mut().tick();
+        assert_eq!(button.borrow().state, ButtonState::Normal);
+    }
+}
@@ -0,0 +1,350 @@
+use crate::componets::component::Component;
+use crate::componets::overlay::Overlay;
+use crate::events::AppEventSender;
+use crate::widgets::{self, Emitter, Widget};
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Paragraph, Widget as UiWidget, Wrap},
+};
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::{Input, InputRequest};
+
+#[derive(Debug)]
+enum Kind {
+    Alert,
+    Confirm,
+    Prompt(Input),
+}
+
+/// How a dialog was closed.
+#[derive(Debug)]
+enum Answer {
+    Ok,
+    Cancel,
+    Text(String),
+}
+
+/// A modal alert, confirm or prompt dialog that sends its answer as an application message.
+///
+/// Left and Right (or Tab) select a button, Enter presses it, Esc cancels and `y`/`n` answer a
+/// confirm dialog. Once answered the dialog ignores all input and returns
+/// [`WidgetEvent::Closed`](widgets::WidgetEvent::Closed), so it can be removed from the tree.
+///
+/// Show it with [`Dialog::component`]. The dialog starts focused and captures the input while
+/// it is in the tree, see [`Component::focused`].
+#[derive(Debug)]
+pub struct Dialog {
+    title: String,
+    message: String,
+    kind: Kind,
+    selected: usize,
+    focused: bool,
+    open: bool,
+    on_answer: Emitter<Answer>,
+}
+
+impl Dialog {
+    fn new(title: &str, message: &str, kind: Kind, on_answer: Emitter<Answer>) -> Self {
+        Self {
+            title: String::from(title),
+            message: String::from(message),
+            kind,
+            selected: 0,
+            focused: true,
+            open: true,
+            on_answer,
+        }
+    }
+
+    /// A message with an OK button, sends `message()` when it is closed.
+    pub fn alert<M: 'static>(
+        title: &str,
+        text: &str,
+        sender: &AppEventSender<M>,
+        message: impl Fn() -> M + 'static,
+    ) -> Self {
+        let on_answer = Emitter::new(sender, move |_| message());
+        Self::new(title, text, Kind::Alert, on_answer)
+    }
+
+    /// A yes/no question, sends `message(true)` if it was confirmed.
+    pub fn confirm<M: 'static>(
+        title: &str,
+        question: &str,
+        sender: &AppEventSender<M>,
+        message: impl Fn(bool) -> M + 'static,
+    ) -> Self {
+        let on_answer = Emitter::new(sender, move |answer| message(matches!(answer, Answer::Ok)));
+        Self::new(title, question, Kind::Confirm, on_answer)
+    }
+
+    /// A single line of text, sends `message(None)` if it was cancelled.
+    pub fn prompt<M: 'static>(
+        title: &str,
+        label: &str,
+        sender: &AppEventSender<M>,
+        message: impl Fn(Option<String>) -> M + 'static,
+    ) -> Self {
+        let on_answer = Emitter::new(sender, move |answer| match answer {
+            Answer::Text(text) => message(Some(text)),
+            Answer::Ok | Answer::Cancel => message(None),
+        });
+        Self::new(title, label, Kind::Prompt(Input::default()), on_answer)
+    }
+
+    /// Wraps the dialog in a centered [modal](Overlay::modal) overlay.
+    pub fn component(self) -> Component {
+        let width = (self.message.chars().count() as u16 + 4).clamp(30, 60);
+        Component::from(self).floating(Overlay::modal(
+            Constraint::Length(width),
+            Constraint::Length(5),
+        ))
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn labels(&self) -> &'static [&'static str] {
+        match self.kind {
+            Kind::Alert => &["OK"],
+            Kind::Confirm => &["Yes", "No"],
+            Kind::Prompt(_) => &["OK", "Cancel"],
+        }
+    }
+
+    /// The areas of the buttons on the last line inside the border, centered.
+    fn buttons(&self, area: Rect) -> Vec<Rect> {
+        let widths: Vec<u16> = self
+            .labels()
+            .iter()
+            .map(|label| label.len() as u16 + 4)
+            .collect();
+        let total = widths.iter().sum::<u16>() + 2 * (widths.len() as u16 - 1);
+        let mut x = area.x + area.width.saturating_sub(total) / 2;
+        let y = area.bottom().saturating_sub(2).max(area.y);
+        widths
+            .into_iter()
+            .map(|width| {
+                let button = Rect::new(x, y, width, 1).intersection(area);
+                x += width + 2;
+                button
+            })
+            .collect()
+    }
+
+    /// Closes the dialog with the button at `index`, 0 accepts and 1 cancels.
+    fn answer(&mut self, index: usize) -> Option<widgets::WidgetEvent> {
+        let answer = match (&self.kind, index) {
+            (Kind::Prompt(input), 0) => Answer::Text(String::from(input.value())),
+            (_, 0) => Answer::Ok,
+            _ => Answer::Cancel,
+        };
+        self.open = false;
+        self.on_answer.emit(answer);
+        Some(widgets::WidgetEvent::Closed)
+    }
+
+    fn select(&mut self, forward: bool) {
+        let count = self.labels().len();
+        self.selected = if forward {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+    }
+}
+
+impl Widget for Dialog {
+    fn clear(&mut self, hard: bool) {
+        self.selected = 0;
+        if let (true, Kind::Prompt(input)) = (hard, &mut self.kind) {
+            input.reset();
+        }
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Tab and the arrow keys select a button or move the cursor of the prompt.
+    fn wants_key(&self, key: KeyEvent) -> bool {
+        self.open
+            && matches!(
+                key.code,
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Left | KeyCode::Right
+            )
+    }
+
+    fn handle_key_event(&mut self, event: KeyEvent) -> Option<widgets::WidgetEvent> {
+        if !self.open || !event.is_press() {
+            return None;
+        }
+        let prompt = matches!(self.kind, Kind::Prompt(_));
+        match event.code {
+            KeyCode::Esc => return self.answer(1),
+            KeyCode::Enter => return self.answer(self.selected),
+            KeyCode::Tab if event.modifiers.contains(KeyModifiers::SHIFT) => self.select(false),
+            KeyCode::Tab => self.select(true),
+            KeyCode::BackTab => self.select(false),
+            KeyCode::Left if !prompt => self.select(false),
+            KeyCode::Right if !prompt => self.select(true),
+            KeyCode::Char('y') if matches!(self.kind, Kind::Confirm) => return self.answer(0),
+            KeyCode::Char('n') if matches!(self.kind, Kind::Confirm) => return self.answer(1),
+            _ => {
+                if let Kind::Prompt(input) = &mut self.kind {
+                    input.handle_event(&Event::Key(event));
+                }
+            }
+        }
+        None
+    }
+
+    /// Presses the clicked button.
+    fn handle_mouse_event(
+        &mut self,
+        event: MouseEvent,
+        area: Rect,
+    ) -> Option<widgets::WidgetEvent> {
+        if !self.open || event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return None;
+        }
+        let position = Position::new(event.column, event.row);
+        let index = self
+            .buttons(area)
+            .iter()
+            .position(|button| button.contains(position))?;
+        self.answer(index)
+    }
+
+    /// Inserts the pasted text into a prompt, line breaks become spaces.
+    fn handle_paste(&mut self, text: &str) -> Option<widgets::WidgetEvent> {
+        if let (true, Kind::Prompt(input)) = (self.open, &mut self.kind) {
+            for c in text.chars().filter(|c| *c != '\r') {
+                input.handle(InputRequest::InsertChar(if c == '\n' { ' ' } else { c }));
+            }
+        }
+        None
+    }
+
+    fn draw(&self, area: Rect, buf: &mut Buffer, ret: &mut Option<u16>) {
+        let border = if self.focused {
+            Color::Yellow
+        } else {
+            Color::Reset
+        };
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().fg(border))
+            .title(self.title.as_str());
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let line = Rect::new(inner.x, inner.y, inner.width, 1).intersection(inner);
+
+        match &self.kind {
+            Kind::Prompt(input) => {
+                let label = format!("{}: ", self.message);
+                let label_width = label.chars().count() as u16;
+                let width = inner.width.saturating_sub(label_width + 1);
+                let scroll = input.visual_scroll(width as usize);
+                let text = Line::from(vec![
+                    label.into(),
+                    input
+                        .value()
+                        .chars()
+                        .skip(scroll)
+                        .collect::<String>()
+                        .into(),
+                ]);
+                buf.set_line(line.x, line.y, &text, line.width);
+                if self.focused && self.open {
+                    // relative to the area, the text starts after the left border
+                    *ret =
+                        Some(1 + label_width + (input.visual_cursor().max(scroll) - scroll) as u16);
+                }
+            }
+            Kind::Alert | Kind::Confirm => {
+                let height = inner.height.saturating_sub(1).max(1);
+                Paragraph::new(self.message.as_str())
+                    .wrap(Wrap { trim: true })
+                    .render(Rect { height, ..inner }.intersection(inner), buf);
+            }
+        }
+
+        for (index, (label, button)) in self.labels().iter().zip(self.buttons(area)).enumerate() {
+            let style = if index == self.selected {
+                Style::new().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::new()
+            };
+            buf.set_stringn(
+                button.x,
+                button.y,
+                format!("[ {label} ]"),
+                button.width as usize,
+                style,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::{Event as AppEvent, test_utils::dummy_event_sender};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[tokio::test]
+    async fn dialogs_answer_once() {
+        let (sender, mut receiver) = dummy_event_sender::<Option<String>>();
+        let sender: AppEventSender<Option<String>> = sender.into();
+        let mut prompt = Dialog::prompt("Rename", "name", &sender, |name| name);
+        prompt.handle_paste("new\nfile");
+        assert_eq!(
+            prompt.handle_key_event(key(KeyCode::Enter)),
+            Some(widgets::WidgetEvent::Closed)
+        );
+        assert!(!prompt.is_open());
+        assert_eq!(prompt.handle_key_event(key(KeyCode::Enter)), None);
+        assert!(matches!(
+            receiver.next().await.unwrap(),
+            AppEvent::Message(Some(name)) if name == "new file"
+        ));
+
+        let (sender, mut receiver) = dummy_event_sender::<bool>();
+        let sender: AppEventSender<bool> = sender.into();
+        let mut confirm = Dialog::confirm("Quit", "Really quit?", &sender, |yes| yes);
+        let area = Rect::new(0, 0, 30, 5);
+        // Yes and No are centered on the line above the bottom border
+        assert_eq!(
+            confirm.buttons(area),
+            [Rect::new(7, 3, 7, 1), Rect::new(16, 3, 6, 1)]
+        );
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 17,
+            row: 3,
+            modifiers: KeyModifiers::NONE,
+        };
+        confirm.handle_mouse_event(click, area);
+        assert!(matches!(
+            receiver.next().await.unwrap(),
+            AppEvent::Message(false)
+        ));
+    }
+}
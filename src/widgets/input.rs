@@ -1,12 +1,16 @@
-use crate::utils;
-use crate::widgets::{self, Widget};
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use crate::clipboard::SharedClipboard;
+use crate::events::AppEventSender;
+use crate::widgets::{self, Emitter, Widget};
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Color, Style},
     widgets::{Block, BorderType, Paragraph, Widget as UiWidget},
 };
+use serde::{Deserialize, Serialize};
 
 use tui_input::Input;
 use tui_input::backend::crossterm::EventHandler;
@@ -24,31 +28,59 @@ pub enum InputType {
     Password,
 }
 
+/// Persisted part of an [`InputWidget`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InputState {
+    value: String,
+    cursor: usize,
+}
+
 #[derive(Debug)]
 pub struct InputWidget {
+    id: Option<String>,
     titel: String,
     input_type: InputType,
     input_mode: InputMode,
     input: Input,
-    on_enter_id: Option<String>,
+    on_enter: Option<Emitter<String>>,
     clear_on_enter: bool,
-    use_clipboard: bool,
+    clipboard: Option<SharedClipboard>,
 }
 
 impl InputWidget {
-    pub fn new(titel: &str, on_enter: &str) -> Self {
+    pub fn new(titel: &str) -> Self {
         Self {
+            id: None,
             titel: String::from(titel),
             input_type: InputType::Text,
             input_mode: InputMode::default(),
             input: Input::default(),
-            on_enter_id: Some(on_enter.to_uppercase().to_owned()),
+            on_enter: None,
             clear_on_enter: false,
-            use_clipboard: false,
+            clipboard: None,
         }
     }
-    pub fn with_clippboard(mut self, clipboard: bool) -> Self {
-        self.use_clipboard = clipboard;
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(String::from(id));
+        self
+    }
+    /// Sends the message built from the content by `message` through `sender` whenever the
+    /// input is submitted with enter.
+    pub fn on_enter<M: 'static>(
+        mut self,
+        sender: &AppEventSender<M>,
+        message: impl Fn(String) -> M + 'static,
+    ) -> Self {
+        self.on_enter = Some(Emitter::new(sender, message));
+        self
+    }
+    /// Enables copy (Ctrl+Insert), cut (Ctrl+X) and paste (Ctrl+V) with `clipboard`.
+    ///
+    /// Copy is not on Ctrl+C because the [default keymap](crate::events::keymap::Keymap::default)
+    /// binds it to quit before any widget sees it, and most terminals send Ctrl+Shift+C the same
+    /// way. Inputs have no selection, so copy and cut take the whole content.
+    pub fn with_clipboard(mut self, clipboard: SharedClipboard) -> Self {
+        self.clipboard = Some(clipboard);
         self
     }
 
@@ -66,6 +98,27 @@ impl InputWidget {
         String::from(self.input.value())
     }
 
+    /// Copies the content to the clipboard, password inputs are never copied.
+    pub fn copy(&self) -> color_eyre::Result<()> {
+        match (&self.clipboard, &self.input_type) {
+            (Some(clipboard), InputType::Text) => clipboard.borrow_mut().write(self.input.value()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Moves the content to the clipboard, password inputs are only cleared.
+    pub fn cut(&mut self) -> color_eyre::Result<()> {
+        self.copy()?;
+        self.input.reset();
+        Ok(())
+    }
+
+    /// Inserts the text of the clipboard at the cursor.
+    pub fn paste(&mut self) -> Option<widgets::WidgetEvent> {
+        let text = self.clipboard.as_ref()?.borrow_mut().read()?;
+        self.handle_paste(&text)
+    }
+
     fn start_editing(&mut self) {
         self.input_mode = InputMode::Editing
     }
@@ -89,37 +142,106 @@ impl Widget for InputWidget {
     fn unfocus(&mut self) {
         self.stop_editing();
     }
+    fn is_focused(&self) -> bool {
+        self.input_mode == InputMode::Editing
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Password inputs are never persisted.
+    fn save_state(&self) -> Option<serde_json::Value> {
+        if let InputType::Password = self.input_type {
+            return None;
+        }
+        serde_json::to_value(InputState {
+            value: self.get_content(),
+            cursor: self.input.cursor(),
+        })
+        .ok()
+    }
+    fn restore_state(&mut self, state: serde_json::Value) {
+        if let Ok(state) = serde_json::from_value::<InputState>(state) {
+            let cursor = state.cursor.min(state.value.chars().count());
+            self.input = Input::new(state.value).with_cursor(cursor);
+        }
+    }
+
+    /// Left and Right move the text cursor.
+    fn wants_key(&self, key: KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Left | KeyCode::Right)
+    }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<widgets::WidgetEvent> {
         if KeyCode::Enter == key_event.code && key_event.is_press() {
-            if let Some(event_id) = self.on_enter_id.as_ref() {
-                let content = self.get_content().clone();
-                if self.clear_on_enter {
-                    self.input.reset();
-                }
-                return Some(widgets::WidgetEvent::Input((
-                    event_id.to_string(),
-                    Some(content),
-                )));
+            let content = self.get_content();
+            if self.clear_on_enter {
+                self.input.reset();
+            }
+            if let Some(on_enter) = self.on_enter.as_ref() {
+                on_enter.emit(content.clone());
             }
+            return Some(widgets::WidgetEvent::Input(content));
         }
-        if self.use_clipboard
-            && KeyCode::Char('v') == key_event.code
+        if self.clipboard.is_some()
             && key_event.is_press()
             && key_event.modifiers.contains(KeyModifiers::CONTROL)
         {
-            if let Some(content) = utils::get_clipboard_content() {
-                let mut value = self.input.value().to_string();
-                let cursor = self.input.cursor();
-                value.insert_str(cursor, &content);
-                self.input = Input::new(value).with_cursor(cursor);
-                return None;
+            // a failed copy leaves the content alone, there is nobody to report it to
+            match key_event.code {
+                KeyCode::Insert => {
+                    let _ = self.copy();
+                    return None;
+                }
+                KeyCode::Char('x') => {
+                    let _ = self.cut();
+                    return None;
+                }
+                KeyCode::Char('v') => return self.paste(),
+                _ => {}
             }
         }
         self.input.handle_event(&Event::Key(key_event));
         None
     }
 
+    /// Inserts the pasted text at the cursor, line breaks become spaces.
+    fn handle_paste(&mut self, text: &str) -> Option<widgets::WidgetEvent> {
+        let text: String = text
+            .chars()
+            .filter(|c| *c != '\r')
+            .map(|c| if c == '\n' { ' ' } else { c })
+            .collect();
+        let value = self.input.value();
+        let cursor = self.input.cursor();
+        let split = value
+            .char_indices()
+            .nth(cursor)
+            .map_or(value.len(), |(index, _)| index);
+        let value = format!("{}{text}{}", &value[..split], &value[split..]);
+        self.input = Input::new(value).with_cursor(cursor + text.chars().count());
+        None
+    }
+
+    /// Moves the cursor to the clicked character.
+    fn handle_mouse_event(
+        &mut self,
+        event: MouseEvent,
+        area: Rect,
+    ) -> Option<widgets::WidgetEvent> {
+        if event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return None;
+        }
+        let width = area.width.max(3) - 3;
+        let scroll = self.input.visual_scroll(width as usize);
+        // the content starts after the left border
+        let column = event.column.saturating_sub(area.x + 1) as usize;
+        let cursor = (scroll + column).min(self.input.value().chars().count());
+        self.input = Input::new(self.get_content()).with_cursor(cursor);
+        None
+    }
+
     fn draw(&self, area: Rect, buf: &mut Buffer, ret: &mut Option<u16>) {
         let style = match self.input_mode {
             InputMode::Normal => Style::default(),
@@ -155,3 +277,65 @@ impl Widget for InputWidget {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clipboard::{self, MemoryClipboard};
+    use crate::componets::component::Component;
+    use std::rc::Rc;
+
+    #[test]
+    fn paste_into_focused_input() {
+        let name = InputWidget::new("name").boxed();
+        let other = InputWidget::new("other").boxed();
+        let root = Component::ListView(Rc::from([
+            Component::Widget(other.clone()),
+            Component::Widget(name.clone()),
+        ]));
+        name.borrow_mut()
+            .restore_state(serde_json::json!({ "value": "äö", "cursor": 1 }));
+        name.borrow_mut().focus();
+
+        root.handle_paste("first\r\nsecond");
+        let name = name.borrow();
+        assert_eq!(name.get_content(), "äfirst secondö");
+        assert_eq!(name.input.cursor(), 13);
+        assert_eq!(other.borrow().get_content(), "");
+    }
+
+    #[test]
+    fn passwords_are_not_saved() {
+        let mut name = InputWidget::new("name");
+        let mut password = InputWidget::new("password").password();
+        name.handle_paste("bob");
+        password.handle_paste("secret");
+        assert!(name.save_state().is_some());
+        assert_eq!(password.save_state(), None);
+    }
+
+    #[test]
+    fn copy_cut_paste() {
+        let clipboard = clipboard::shared(MemoryClipboard::new());
+        let mut input = InputWidget::new("name").with_clipboard(clipboard.clone());
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        let copy = KeyEvent::new(KeyCode::Insert, KeyModifiers::CONTROL);
+        input.handle_paste("abc");
+        input.handle_key_event(ctrl('x'));
+        assert_eq!(input.get_content(), "");
+        assert_eq!(clipboard.borrow_mut().read().as_deref(), Some("abc"));
+        input.handle_key_event(ctrl('v'));
+        input.handle_key_event(ctrl('v'));
+        assert_eq!(input.get_content(), "abcabc");
+        input.handle_key_event(copy);
+        assert_eq!(input.get_content(), "abcabc");
+        assert_eq!(clipboard.borrow_mut().read().as_deref(), Some("abcabc"));
+
+        let mut password = InputWidget::new("password")
+            .password()
+            .with_clipboard(clipboard.clone());
+        password.handle_paste("secret");
+        password.handle_key_event(copy);
+        assert_eq!(clipboard.borrow_mut().read().as_deref(), Some("abcabc"));
+    }
+}
@@ -1,7 +1,8 @@
-use crossterm::event::KeyEvent;
+use crate::events::AppEventSender;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{buffer::Buffer, layout::Rect};
 use std::cell::RefCell;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use std::rc::Rc;
 
 pub mod input;
@@ -10,10 +11,59 @@ pub mod button;
 pub use button::*;
 pub mod color;
 pub use color::*;
+pub mod dialog;
+pub use dialog::*;
+pub mod tasks;
+pub use tasks::*;
 
+/// What happened inside a widget.
+///
+/// The typed message of the application is delivered separately through the [`Emitter`] of
+/// the widget.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WidgetEvent {
-    Input((String, Option<String>)),
-    Button(String),
+    /// An input was submitted with the given content.
+    Input(String),
+    /// A button was pressed.
+    Button,
+    /// A dialog was answered and can be removed.
+    Closed,
+}
+
+/// Sends a typed application message through an [`AppEventSender`] when a widget is triggered.
+///
+/// `T` is the value the widget hands to the message constructor, e.g. the content of an input.
+pub struct Emitter<T = ()> {
+    emit: Rc<dyn Fn(T)>,
+}
+
+impl<T> Emitter<T> {
+    /// Creates an emitter that sends the message built by `message` through `sender`.
+    pub fn new<M: 'static>(sender: &AppEventSender<M>, message: impl Fn(T) -> M + 'static) -> Self {
+        let sender = sender.clone();
+        Self {
+            emit: Rc::new(move |value| sender.send_message(message(value))),
+        }
+    }
+
+    pub fn emit(&self, value: T) {
+        (self.emit)(value)
+    }
+}
+
+// derived Clone would require `T: Clone`
+impl<T> Clone for Emitter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            emit: self.emit.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Emitter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Emitter")
+    }
 }
 
 pub trait Widget: Debug {
@@ -24,17 +74,61 @@ pub trait Widget: Debug {
         Rc::new(RefCell::new(self))
     }
 
+    /// Stable identifier of the widget, used to persist and restore its state.
+    fn id(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether the [`FocusManager`](crate::componets::focus::FocusManager) may move the focus
+    /// to this widget.
+    fn focusable(&self) -> bool {
+        true
+    }
     fn focus(&mut self) {}
     fn unfocus(&mut self) {}
+    fn is_focused(&self) -> bool {
+        false
+    }
+
+    /// Returns the part of the widget state that should survive a restart.
+    fn save_state(&self) -> Option<serde_json::Value> {
+        None
+    }
+    /// Restores a state previously returned by [`Widget::save_state`].
+    fn restore_state(&mut self, _: serde_json::Value) {}
 
     fn handle_key_event(&mut self, _: KeyEvent) -> Option<WidgetEvent> {
         None
     }
 
+    /// Called for every [`Event::Tick`](crate::events::Event::Tick) the runner receives, e.g.
+    /// to end a transient state.
+    fn tick(&mut self) {}
+
+    /// Whether the widget uses `key` itself while it has focus, e.g. Left and Right to move a
+    /// text cursor. Such keys are not used to move the focus.
+    fn wants_key(&self, _: KeyEvent) -> bool {
+        false
+    }
+
+    /// Handles a mouse event inside the widget, `area` is where the widget was drawn.
+    ///
+    /// Drags and releases are delivered to the widget the button was pressed on, even if they
+    /// happen outside of it.
+    fn handle_mouse_event(&mut self, _: MouseEvent, _area: Rect) -> Option<WidgetEvent> {
+        None
+    }
+
+    /// Handles text pasted while the widget has focus.
+    fn handle_paste(&mut self, _: &str) -> Option<WidgetEvent> {
+        None
+    }
+
     fn clear(&mut self, hard: bool);
 
     fn draw(&self, area: Rect, buf: &mut Buffer, ret: &mut Option<u16>);
 
+    #[allow(clippy::wrong_self_convention)]
     fn into_widget(&self) -> &dyn Widget
     where
         Self: Sized,
@@ -0,0 +1,214 @@
+use crate::events::task::{RunningTask, TaskManager};
+use crate::widgets::{Widget, WidgetEvent};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Widget as w},
+};
+use std::fmt;
+
+/// Width of the progress bar, without the brackets.
+const BAR_WIDTH: usize = 10;
+
+/// Lists the running background tasks with their progress.
+///
+/// Up and Down select a task, Delete or `x` cancel it.
+pub struct TaskList<M = ()> {
+    tasks: TaskManager<M>,
+    selected: usize,
+    focused: bool,
+}
+
+impl<M: Send + 'static> TaskList<M> {
+    pub fn new(tasks: TaskManager<M>) -> Self {
+        Self {
+            tasks,
+            selected: 0,
+            focused: false,
+        }
+    }
+
+    fn selected(&self, running: &[RunningTask]) -> usize {
+        self.selected.min(running.len().saturating_sub(1))
+    }
+}
+
+impl<M> fmt::Debug for TaskList<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskList")
+            .field("tasks", &self.tasks)
+            .field("selected", &self.selected)
+            .finish_non_exhaustive()
+    }
+}
+
+/// `[#####     ]  50%`, or a bar without a percentage if the task did not report any.
+fn bar(progress: Option<f64>) -> String {
+    match progress {
+        Some(progress) => {
+            let filled = ((progress * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+            format!(
+                "[{}{}] {:>3.0}%",
+                "#".repeat(filled),
+                " ".repeat(BAR_WIDTH - filled),
+                progress * 100.0
+            )
+        }
+        None => format!("[{}]     ", "~".repeat(BAR_WIDTH)),
+    }
+}
+
+impl<M: Send + 'static> Widget for TaskList<M> {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn clear(&mut self, _: bool) {
+        self.selected = 0;
+    }
+
+    fn wants_key(&self, key: KeyEvent) -> bool {
+        matches!(
+            key.code,
+            KeyCode::Up | KeyCode::Down | KeyCode::Delete | KeyCode::Char('x')
+        )
+    }
+
+    fn handle_key_event(&mut self, event: KeyEvent) -> Option<WidgetEvent> {
+        if !event.is_press() {
+            return None;
+        }
+        let running = self.tasks.running();
+        let selected = self.selected(&running);
+        match event.code {
+            KeyCode::Up => self.selected = selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (selected + 1).min(running.len().saturating_sub(1)),
+            KeyCode::Delete | KeyCode::Char('x') => {
+                if let Some(task) = running.get(selected) {
+                    self.tasks.cancel(task.id);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn draw(&self, area: Rect, buf: &mut Buffer, _: &mut Option<u16>) {
+        let running = self.tasks.running();
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(format!("Tasks ({})", running.len()));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let selected = self.selected(&running);
+        for (row, (index, task)) in (inner.y..inner.bottom()).zip(running.iter().enumerate()) {
+            let style = if self.focused && index == selected {
+                Style::new().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::new()
+            };
+            let line = Line::from(vec![
+                Span::raw(format!("{} ", task.name)),
+                Span::styled(bar(task.progress), Style::new().fg(Color::Cyan)),
+                Span::styled(
+                    format!(" {}", task.message.as_deref().unwrap_or_default()),
+                    Style::new().fg(Color::DarkGray),
+                ),
+            ])
+            .style(style);
+            buf.set_line(inner.x, row, &line, inner.width);
+        }
+    }
+
+    fn get_len(&self) -> usize {
+        self.tasks.running().len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::task::TaskEvent;
+    use crate::events::test_utils::dummy_event_sender;
+    use crate::events::{AppEvent, AppEventSender, Event};
+    use crossterm::event::KeyModifiers;
+    use tokio::sync::oneshot;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn row(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect()
+    }
+
+    #[tokio::test]
+    async fn tasks_select_cancel_and_draw() {
+        let (sender, mut receiver) = dummy_event_sender::<()>();
+        let tasks = AppEventSender::from(sender).tasks();
+        let (_go_a, wait_a) = oneshot::channel::<()>();
+        let (go_c, wait_c) = oneshot::channel::<()>();
+        tasks.spawn("a", |progress| async move {
+            progress.set(0.5);
+            wait_a.await?;
+            Ok(())
+        });
+        tasks.spawn("b", |_| std::future::pending());
+        tasks.spawn("c", |_| async move {
+            wait_c.await?;
+            Ok(())
+        });
+        // three starts and the progress of a
+        for _ in 0..4 {
+            receiver.next().await.unwrap();
+        }
+
+        let mut list = TaskList::new(tasks.clone());
+        list.focus();
+        for code in [
+            KeyCode::Up,
+            KeyCode::Down,
+            KeyCode::Delete,
+            KeyCode::Char('x'),
+        ] {
+            assert!(list.wants_key(key(code)));
+        }
+        assert!(!list.wants_key(key(KeyCode::Enter)));
+        list.handle_key_event(key(KeyCode::Down));
+        list.handle_key_event(key(KeyCode::Down));
+        list.handle_key_event(key(KeyCode::Down));
+        assert_eq!(list.selected(&tasks.running()), 2);
+
+        // the selection moves up to the last task when the selected one finishes
+        go_c.send(()).unwrap();
+        while !matches!(
+            receiver.next().await.unwrap(),
+            Event::App(AppEvent::Task(TaskEvent::Completed(_)))
+        ) {}
+        let area = Rect::new(0, 0, 40, 4);
+        let mut buf = Buffer::empty(area);
+        list.draw(area, &mut buf, &mut None);
+        assert!(row(&buf, 0).contains("Tasks (2)"));
+        assert!(row(&buf, 1).starts_with("│a [#####     ]  50%"));
+        assert!(row(&buf, 2).starts_with("│b [~~~~~~~~~~]"));
+        assert!(buf[(1, 2)].modifier.contains(Modifier::REVERSED));
+        assert!(!buf[(1, 1)].modifier.contains(Modifier::REVERSED));
+
+        list.handle_key_event(key(KeyCode::Char('x')));
+        assert_eq!(tasks.running().len(), 1);
+        assert_eq!(tasks.running()[0].name, "a");
+        list.handle_key_event(key(KeyCode::Delete));
+        assert!(tasks.running().is_empty());
+        // nothing left to cancel
+        list.handle_key_event(key(KeyCode::Delete));
+    }
+}